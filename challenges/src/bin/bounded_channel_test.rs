@@ -0,0 +1,203 @@
+use std::thread;
+use std::time::Duration;
+
+use challenges::bounded_channel::BoundedChannel;
+
+fn main() {
+    let channel = BoundedChannel::<u32, 2>::new();
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            for frame in 0..6 {
+                // Blocks while the processing stage is behind
+                channel.send(frame).unwrap();
+                println!("Sent frame {}", frame);
+            }
+            channel.close();
+        });
+
+        s.spawn(|| {
+            while let Some(frame) = channel.recv() {
+                thread::sleep(Duration::from_millis(10));
+                println!("Processed frame {}", frame);
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use challenges::bounded_channel::{
+        BoundedChannel, RecvTimeoutError, SendError, SendTimeoutError, TryRecvError, TrySendError,
+    };
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+    use std::time::Duration;
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Minimal executor that parks the current thread until the future is woken.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn try_send_and_try_recv() {
+        let channel = BoundedChannel::<i32, 1>::new();
+        assert_eq!(channel.try_recv(), Err(TryRecvError::Empty));
+        assert_eq!(channel.try_send(1), Ok(()));
+        assert_eq!(channel.try_send(2), Err(TrySendError::Full(2)));
+        assert_eq!(channel.try_recv(), Ok(1));
+        channel.close();
+        assert_eq!(channel.try_send(3), Err(TrySendError::Closed(3)));
+        assert_eq!(channel.try_recv(), Err(TryRecvError::Closed));
+    }
+
+    #[test]
+    fn close_lets_receivers_drain() {
+        let channel = BoundedChannel::<i32, 4>::new();
+        channel.send(1).unwrap();
+        channel.send(2).unwrap();
+        channel.close();
+        assert_eq!(channel.send(3), Err(SendError(3)));
+        assert_eq!(channel.recv(), Some(1));
+        assert_eq!(channel.recv(), Some(2));
+        assert_eq!(channel.recv(), None);
+    }
+
+    #[test]
+    fn timeouts_expire() {
+        let channel = BoundedChannel::<i32, 1>::new();
+        assert_eq!(
+            channel.recv_timeout(Duration::from_millis(10)),
+            Err(RecvTimeoutError::Timeout)
+        );
+        channel.send(1).unwrap();
+        assert_eq!(
+            channel.send_timeout(2, Duration::from_millis(10)),
+            Err(SendTimeoutError::Timeout(2))
+        );
+        assert_eq!(channel.recv_timeout(Duration::from_millis(10)), Ok(1));
+    }
+
+    #[test]
+    fn huge_timeouts_do_not_overflow() {
+        let channel = BoundedChannel::<i32, 1>::new();
+        assert_eq!(channel.send_timeout(1, Duration::MAX), Ok(()));
+        assert_eq!(channel.recv_timeout(Duration::MAX), Ok(1));
+
+        channel.send(2).unwrap();
+        channel.close();
+        assert_eq!(
+            channel.send_timeout(3, Duration::MAX),
+            Err(SendTimeoutError::Closed(3))
+        );
+        assert_eq!(channel.recv_timeout(Duration::MAX), Ok(2));
+        assert_eq!(
+            channel.recv_timeout(Duration::MAX),
+            Err(RecvTimeoutError::Closed)
+        );
+    }
+
+    #[test]
+    fn send_blocks_until_space() {
+        let channel = BoundedChannel::<usize, 2>::new();
+        thread::scope(|s| {
+            s.spawn(|| {
+                for i in 0..1000 {
+                    channel.send(i).unwrap();
+                }
+                channel.close();
+            });
+            let received: Vec<usize> = std::iter::from_fn(|| channel.recv()).collect();
+            assert_eq!(received, (0..1000).collect::<Vec<_>>());
+        });
+    }
+
+    #[test]
+    fn send_timeout_succeeds_when_space_frees() {
+        let channel = BoundedChannel::<i32, 1>::new();
+        channel.send(1).unwrap();
+        thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(20));
+                channel.recv()
+            });
+            assert_eq!(channel.send_timeout(2, Duration::from_secs(5)), Ok(()));
+        });
+        assert_eq!(channel.recv(), Some(2));
+    }
+
+    #[test]
+    fn async_send_and_recv() {
+        let channel = BoundedChannel::<usize, 2>::new();
+        thread::scope(|s| {
+            s.spawn(|| {
+                block_on(async {
+                    for i in 0..500 {
+                        channel.send_async(i).await.unwrap();
+                    }
+                });
+                channel.close();
+            });
+            let received = block_on(async {
+                let mut received = Vec::new();
+                while let Some(item) = channel.recv_async().await {
+                    received.push(item);
+                }
+                received
+            });
+            assert_eq!(received, (0..500).collect::<Vec<_>>());
+        });
+    }
+
+    #[test]
+    fn async_and_blocking_interoperate() {
+        let channel = BoundedChannel::<usize, 1>::new();
+        thread::scope(|s| {
+            s.spawn(|| {
+                for i in 0..200 {
+                    channel.send(i).unwrap();
+                }
+                channel.close();
+            });
+            let count = block_on(async {
+                let mut count = 0;
+                while channel.recv_async().await.is_some() {
+                    count += 1;
+                }
+                count
+            });
+            assert_eq!(count, 200);
+        });
+    }
+
+    #[test]
+    fn close_wakes_pending_future() {
+        let channel = BoundedChannel::<i32, 1>::new();
+        thread::scope(|s| {
+            s.spawn(|| {
+                thread::sleep(Duration::from_millis(20));
+                channel.close();
+            });
+            assert_eq!(block_on(channel.recv_async()), None);
+        });
+    }
+}
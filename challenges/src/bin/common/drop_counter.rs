@@ -0,0 +1,23 @@
+//! Test fixture shared by the container test bins through `#[path]`.
+
+use std::cell::Cell;
+
+/// Bumps `drops` when dropped, to check that a container drops each item exactly once.
+pub struct DropCounter<'a> {
+    /// Tells items apart, not every bin reads it
+    #[allow(dead_code)]
+    pub value: i32,
+    drops: &'a Cell<usize>,
+}
+
+impl<'a> DropCounter<'a> {
+    pub fn new(value: i32, drops: &'a Cell<usize>) -> Self {
+        Self { value, drops }
+    }
+}
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
@@ -0,0 +1,307 @@
+use challenges::serialization::{from_bytes, to_bytes, Serializable};
+
+#[derive(Serializable, Debug, PartialEq)]
+enum Shape {
+    Circle { radius: f32 },
+    Rectangle(f32, f32),
+    Empty,
+}
+
+fn main() {
+    let shapes = vec![
+        Shape::Circle { radius: 1.5 },
+        Shape::Rectangle(2.0, 3.0),
+        Shape::Empty,
+    ];
+    let bytes = to_bytes(&shapes).unwrap();
+    println!("Encoded {} bytes: {:?}", bytes.len(), bytes);
+    let decoded: Vec<Shape> = from_bytes(&bytes).unwrap();
+    println!("Decoded: {:?}", decoded);
+}
+
+#[cfg(test)]
+mod tests {
+    use challenges::serialization::{
+        from_bytes, to_bytes, DecodeError, Deserializer, Serializable, Serializer,
+        DEFAULT_MAX_DEPTH, MAGIC,
+    };
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::fmt::Debug;
+
+    const HEADER_LEN: usize = MAGIC.len() + 2;
+
+    #[derive(Serializable, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        y: f32,
+        label: String,
+    }
+
+    #[derive(Serializable, Debug, PartialEq)]
+    struct Meters(f64);
+
+    #[derive(Serializable, Debug, PartialEq)]
+    struct Marker;
+
+    #[derive(Serializable, Debug, PartialEq)]
+    struct Pair<A, B> {
+        first: A,
+        second: B,
+    }
+
+    #[derive(Serializable, Debug, PartialEq)]
+    enum Command {
+        Stop,
+        Move { x: i32, y: i32 },
+        Say(String),
+        Batch(Vec<Command>),
+    }
+
+    fn round_trip<T: Serializable + PartialEq + Debug>(value: T) -> Vec<u8> {
+        let bytes = to_bytes(&value).unwrap();
+        assert_eq!(from_bytes::<T>(&bytes).unwrap(), value);
+        bytes[HEADER_LEN..].to_vec()
+    }
+
+    fn encode<T: Serializable>(value: &T) -> Vec<u8> {
+        let mut serializer = Serializer::new(Vec::new());
+        serializer.serialize(value).unwrap();
+        serializer.into_inner()
+    }
+
+    #[test]
+    fn named_struct_encodes_fields_in_order() {
+        let body = round_trip(Point {
+            x: 1.0,
+            y: -2.0,
+            label: "a".to_string(),
+        });
+        let mut expected = encode(&1.0f32);
+        expected.extend(encode(&-2.0f32));
+        expected.extend(encode(&"a".to_string()));
+        assert_eq!(body, expected);
+    }
+
+    #[test]
+    fn tuple_and_unit_structs() {
+        assert_eq!(round_trip(Meters(2.5)), encode(&2.5f64));
+        assert!(round_trip(Marker).is_empty());
+        round_trip(Pair {
+            first: 7u8,
+            second: vec![Meters(1.0)],
+        });
+    }
+
+    #[test]
+    fn enums_write_a_u32_tag_then_fields() {
+        assert_eq!(round_trip(Command::Stop), encode(&0u32));
+
+        let mut expected = encode(&1u32);
+        expected.extend(encode(&3i32));
+        expected.extend(encode(&-4i32));
+        assert_eq!(round_trip(Command::Move { x: 3, y: -4 }), expected);
+
+        round_trip(Command::Say("hi".to_string()));
+        round_trip(Command::Batch(vec![
+            Command::Stop,
+            Command::Batch(vec![Command::Move { x: 1, y: 2 }]),
+        ]));
+    }
+
+    #[test]
+    fn unknown_variant_is_an_error() {
+        let mut bytes = to_bytes(&vec![Command::Stop, Command::Stop]).unwrap();
+        let offset = bytes.len() - 4;
+        bytes[offset] = 9;
+        let error = from_bytes::<Vec<Command>>(&bytes).unwrap_err();
+        assert!(matches!(
+            error,
+            DecodeError::UnknownVariant { offset: o, tag: 9 } if o == offset
+        ));
+    }
+
+    #[test]
+    fn skipped_fields_are_not_written() {
+        #[derive(Serializable, Debug, PartialEq)]
+        struct Cached {
+            id: u16,
+            #[serializable(skip)]
+            cache: Vec<u8>,
+        }
+
+        #[derive(Serializable, Debug, PartialEq)]
+        enum Event {
+            Tick(#[serializable(skip)] u64, u8),
+        }
+
+        let bytes = to_bytes(&Cached {
+            id: 5,
+            cache: vec![1, 2, 3],
+        })
+        .unwrap();
+        assert_eq!(&bytes[HEADER_LEN..], encode(&5u16));
+        assert_eq!(
+            from_bytes::<Cached>(&bytes).unwrap(),
+            Cached {
+                id: 5,
+                cache: Vec::new()
+            }
+        );
+
+        let bytes = to_bytes(&Event::Tick(99, 1)).unwrap();
+        assert_eq!(from_bytes::<Event>(&bytes).unwrap(), Event::Tick(0, 1));
+    }
+
+    #[test]
+    fn default_fields_accept_older_data() {
+        #[derive(Serializable, Debug, PartialEq)]
+        struct ConfigV1 {
+            name: String,
+        }
+
+        #[derive(Serializable, Debug, PartialEq)]
+        struct ConfigV2 {
+            name: String,
+            #[serializable(default)]
+            retries: u32,
+        }
+
+        let old = to_bytes(&ConfigV1 {
+            name: "lidar".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            from_bytes::<ConfigV2>(&old).unwrap(),
+            ConfigV2 {
+                name: "lidar".to_string(),
+                retries: 0
+            }
+        );
+        round_trip(ConfigV2 {
+            name: "lidar".to_string(),
+            retries: 3,
+        });
+
+        // A field cut in half is still an error
+        let mut bytes = to_bytes(&ConfigV2 {
+            name: String::new(),
+            retries: 3,
+        })
+        .unwrap();
+        bytes.pop();
+        assert!(matches!(
+            from_bytes::<ConfigV2>(&bytes).unwrap_err(),
+            DecodeError::UnexpectedEof { .. }
+        ));
+    }
+
+    #[test]
+    fn default_fields_only_extend_the_last_value() {
+        #[derive(Serializable, Debug, PartialEq)]
+        struct OldRecord {
+            id: u32,
+        }
+
+        #[derive(Serializable, Debug, PartialEq)]
+        struct NewRecord {
+            id: u32,
+            #[serializable(default)]
+            weight: u32,
+        }
+
+        // Inside a `Vec` the new field reads the next record instead
+        let old = to_bytes(&(1..=4).map(|id| OldRecord { id }).collect::<Vec<_>>()).unwrap();
+        assert!(matches!(
+            from_bytes::<Vec<NewRecord>>(&old).unwrap_err(),
+            DecodeError::CountMismatch {
+                declared: 4,
+                read: 2
+            }
+        ));
+
+        // Same when old values are streamed through one deserializer
+        let mut stream = encode(&OldRecord { id: 1 });
+        stream.extend(encode(&OldRecord { id: 2 }));
+        let mut deserializer = Deserializer::new(stream.as_slice());
+        assert_eq!(
+            deserializer.deserialize::<NewRecord>().unwrap(),
+            NewRecord { id: 1, weight: 2 }
+        );
+    }
+
+    /// Stands in for a dependent that renamed `challenges` in its `Cargo.toml`.
+    mod renamed {
+        pub use challenges::serialization;
+    }
+
+    #[test]
+    fn crate_path_can_be_overridden() {
+        #[derive(Serializable, Debug, PartialEq)]
+        #[serializable(crate = "self::renamed")]
+        enum Level {
+            Low,
+            High(u8),
+        }
+
+        round_trip(Level::Low);
+        assert_eq!(round_trip(Level::High(7)), [1, 0, 0, 0, 7]);
+    }
+
+    #[test]
+    fn random_input_never_panics() {
+        let mut rng = StdRng::seed_from_u64(24);
+        let original = to_bytes(&vec![
+            Command::Say("go".to_string()),
+            Command::Batch(vec![Command::Move { x: 1, y: 2 }]),
+        ])
+        .unwrap();
+        for _ in 0..2000 {
+            let mut bytes = original.clone();
+            let index = rng.gen_range(HEADER_LEN..bytes.len());
+            if rng.gen_bool(0.5) {
+                bytes[index] = rng.gen();
+            } else {
+                bytes.truncate(index);
+            }
+            let _ = from_bytes::<Vec<Command>>(&bytes);
+            let _ = from_bytes::<Pair<Point, Meters>>(&bytes);
+        }
+    }
+
+    #[test]
+    fn deep_nesting_hits_the_depth_limit() {
+        // `Batch` holding one more `Batch`, about 4 MB deep
+        let mut bytes = to_bytes(&Command::Stop).unwrap();
+        bytes.truncate(HEADER_LEN);
+        for _ in 0..500_000 {
+            bytes.extend([3, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        match from_bytes::<Command>(&bytes).unwrap_err() {
+            DecodeError::DepthLimitExceeded { offset } => {
+                assert!(offset > HEADER_LEN && offset <= HEADER_LEN + 8 * DEFAULT_MAX_DEPTH)
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn depth_limit_is_configurable() {
+        // The outer command, its `Vec` and the inner command each take a level
+        let nested = encode(&Command::Batch(vec![Command::Stop]));
+
+        let mut deserializer = Deserializer::new(nested.as_slice());
+        deserializer.set_max_depth(2);
+        assert!(matches!(
+            deserializer.deserialize::<Command>().unwrap_err(),
+            DecodeError::DepthLimitExceeded { offset: 8 }
+        ));
+
+        let mut deserializer = Deserializer::new(nested.as_slice());
+        deserializer.set_max_depth(3);
+        assert_eq!(
+            deserializer.deserialize::<Command>().unwrap(),
+            Command::Batch(vec![Command::Stop])
+        );
+    }
+}
@@ -1,10 +1,11 @@
 extern crate serde;
 extern crate serde_json;
 
+use challenges::serialization::{from_bytes, to_bytes, Serializable};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Serializable, Debug, PartialEq)]
 struct User {
     id: u32,
     name: String,
@@ -35,5 +36,15 @@ fn main() -> serde_json::Result<()> {
     let deserialized: User = serde_json::from_str(&serialized)?;
     println!("Deserialized User: {:#?}", deserialized);
 
+    // Round trip through the custom binary format
+    let bytes = to_bytes(&user).expect("user fits in the binary format");
+    let decoded: User = from_bytes(&bytes).expect("bytes were just encoded");
+    println!(
+        "Binary: {} bytes vs JSON: {} bytes | Equal: {}",
+        bytes.len(),
+        serialized.len(),
+        decoded == user
+    );
+
     Ok(())
 }
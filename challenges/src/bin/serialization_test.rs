@@ -0,0 +1,611 @@
+use challenges::serialization::{Deserializer, Serializer};
+
+fn main() {
+    // Stream a small scan header followed by its ranges into one buffer
+    let mut serializer = Serializer::new(Vec::new());
+    serializer.serialize(&"front_lidar".to_string()).unwrap();
+    serializer.serialize(&42u64).unwrap();
+    serializer.serialize(&vec![1.5f32, 2.25, 3.0]).unwrap();
+    let bytes = serializer.into_inner();
+    println!("Encoded {} bytes: {:?}", bytes.len(), bytes);
+
+    let mut deserializer = Deserializer::new(bytes.as_slice());
+    let sensor: String = deserializer.deserialize().unwrap();
+    let timestamp: u64 = deserializer.deserialize().unwrap();
+    let ranges: Vec<f32> = deserializer.deserialize().unwrap();
+    println!("{} @ {}: {:?}", sensor, timestamp, ranges);
+}
+
+#[cfg(test)]
+mod tests {
+    use challenges::serialization::{
+        from_bytes, from_reader, to_bytes, to_writer, DecodeError, Deserializer, Endian,
+        Serializable, Serializer, DEFAULT_MAX_DEPTH, MAGIC, VERSION,
+    };
+    use challenges::stack_queue::StackQueue;
+    use challenges::stack_vector::StackVector;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::fmt::Debug;
+    use std::io::{self, Cursor, Read, Write};
+
+    const HEADER_LEN: usize = MAGIC.len() + 2;
+
+    fn round_trip<T: Serializable + PartialEq + Debug>(value: T, encoded_len: usize) {
+        let bytes = to_bytes(&value).unwrap();
+        assert_eq!(bytes.len(), HEADER_LEN + encoded_len);
+        assert_eq!(from_bytes::<T>(&bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn signed_integers() {
+        for value in [i8::MIN, -1, 0, 1, i8::MAX] {
+            round_trip(value, 1);
+        }
+        for value in [i16::MIN, -1, 0, 1, i16::MAX] {
+            round_trip(value, 2);
+        }
+        for value in [i32::MIN, -1, 0, 1, i32::MAX] {
+            round_trip(value, 4);
+        }
+        for value in [i64::MIN, -1, 0, 1, i64::MAX] {
+            round_trip(value, 8);
+        }
+    }
+
+    #[test]
+    fn unsigned_integers() {
+        for value in [0, 1, u8::MAX] {
+            round_trip(value, 1);
+        }
+        for value in [0, 1, u16::MAX] {
+            round_trip(value, 2);
+        }
+        for value in [0, 1, u32::MAX] {
+            round_trip(value, 4);
+        }
+        for value in [0, 1, u64::MAX] {
+            round_trip(value, 8);
+        }
+    }
+
+    #[test]
+    fn floats() {
+        for value in [0.0, -0.0, 1.5, f32::MIN, f32::MAX, f32::INFINITY] {
+            round_trip(value, 4);
+        }
+        for value in [0.0, -0.0, 1.5, f64::MIN, f64::MAX, f64::NEG_INFINITY] {
+            round_trip(value, 8);
+        }
+        let nan = from_bytes::<f64>(&to_bytes(&f64::NAN).unwrap()).unwrap();
+        assert!(nan.is_nan());
+    }
+
+    #[test]
+    fn strings() {
+        round_trip(String::new(), 4);
+        round_trip("Hello, world!".to_string(), 4 + 13);
+        round_trip("Grüße, 世界".to_string(), 4 + "Grüße, 世界".len());
+    }
+
+    #[test]
+    fn vectors() {
+        round_trip(Vec::<u8>::new(), 4);
+        round_trip(vec![1i32, -2, 3], 4 + 3 * 4);
+        round_trip(
+            vec!["Hello".to_string(), String::new(), "I am fine!".to_string()],
+            4 + (4 + 5) + 4 + (4 + 10),
+        );
+        round_trip(
+            vec![vec![1u16], vec![], vec![2, 3]],
+            4 + (4 + 2) + 4 + (4 + 4),
+        );
+    }
+
+    #[test]
+    fn bools_and_chars() {
+        round_trip(false, 1);
+        round_trip(true, 1);
+        for value in ['a', 'é', '世', '\u{1f980}', char::MAX] {
+            round_trip(value, 4);
+        }
+
+        let mut bytes = to_bytes(&true).unwrap();
+        bytes[HEADER_LEN] = 2;
+        assert!(matches!(
+            from_bytes::<bool>(&bytes).unwrap_err(),
+            DecodeError::InvalidBool {
+                offset: HEADER_LEN,
+                byte: 2
+            }
+        ));
+
+        // Surrogates are not scalar values
+        let mut bytes = to_bytes(&'a').unwrap();
+        bytes[HEADER_LEN..].copy_from_slice(&0xd800u32.to_le_bytes());
+        assert!(matches!(
+            from_bytes::<char>(&bytes).unwrap_err(),
+            DecodeError::InvalidChar {
+                offset: HEADER_LEN,
+                value: 0xd800
+            }
+        ));
+    }
+
+    #[test]
+    fn options_and_boxes() {
+        round_trip(None::<u32>, 1);
+        round_trip(Some(7u32), 1 + 4);
+        round_trip(Some(None::<u8>), 1 + 1);
+        round_trip(vec![Some("a".to_string()), None], 4 + (1 + 4 + 1) + 1);
+        round_trip(Box::new(3u16), 2);
+        assert_eq!(to_bytes(&Box::new(3u16)).unwrap(), to_bytes(&3u16).unwrap());
+
+        let mut bytes = to_bytes(&Some(1u8)).unwrap();
+        bytes[HEADER_LEN] = 5;
+        assert!(matches!(
+            from_bytes::<Option<u8>>(&bytes).unwrap_err(),
+            DecodeError::UnknownVariant {
+                offset: HEADER_LEN,
+                tag: 5
+            }
+        ));
+    }
+
+    #[test]
+    fn arrays_and_tuples_have_no_count() {
+        round_trip([1u16, 2, 3], 3 * 2);
+        round_trip([[true; 2]; 3], 6);
+        round_trip(["x".to_string(), String::new()], (4 + 1) + 4);
+        round_trip((1u8,), 1);
+        round_trip((1u8, -2i64, "z".to_string()), 1 + 8 + (4 + 1));
+        round_trip(
+            (
+                1u8, 2u16, 3u32, 4u64, 5i8, 6i16, 7i32, 8i64, 9.0f32, 10.0f64, 'k', true,
+            ),
+            1 + 2 + 4 + 8 + 1 + 2 + 4 + 8 + 4 + 8 + 4 + 1,
+        );
+
+        let mut bytes = to_bytes(&[1u16, 2, 3]).unwrap();
+        bytes.truncate(bytes.len() - 2);
+        assert!(matches!(
+            from_bytes::<[u16; 3]>(&bytes).unwrap_err(),
+            DecodeError::CountMismatch {
+                declared: 3,
+                read: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn maps_and_sets() {
+        let hash_map: HashMap<String, u8> = [("a".to_string(), 1), ("bc".to_string(), 2)].into();
+        round_trip(hash_map, 4 + (4 + 1 + 1) + (4 + 2 + 1));
+        round_trip(HashSet::from([3u32, 1, 2]), 4 + 3 * 4);
+        round_trip(HashMap::<u8, Vec<u8>>::new(), 4);
+
+        // BTreeMap entries come out in key order
+        let tree = BTreeMap::from([(2u8, 'b'), (1, 'a')]);
+        let bytes = to_bytes(&tree).unwrap();
+        assert_eq!(
+            &bytes[HEADER_LEN..],
+            &[2, 0, 0, 0, 1, b'a', 0, 0, 0, 2, b'b', 0, 0, 0]
+        );
+        assert_eq!(from_bytes::<BTreeMap<u8, char>>(&bytes).unwrap(), tree);
+    }
+
+    #[test]
+    fn duplicate_keys_are_rejected() {
+        let pairs = to_bytes(&vec![(1u8, 10u8), (1, 20)]).unwrap();
+        let duplicate_at = HEADER_LEN + 4 + 2;
+        assert!(matches!(
+            from_bytes::<HashMap<u8, u8>>(&pairs).unwrap_err(),
+            DecodeError::DuplicateKey { offset } if offset == duplicate_at
+        ));
+        assert!(matches!(
+            from_bytes::<BTreeMap<u8, u8>>(&pairs).unwrap_err(),
+            DecodeError::DuplicateKey { offset } if offset == duplicate_at
+        ));
+
+        let items = to_bytes(&vec![4u8, 4]).unwrap();
+        assert!(matches!(
+            from_bytes::<HashSet<u8>>(&items).unwrap_err(),
+            DecodeError::DuplicateKey { offset } if offset == HEADER_LEN + 5
+        ));
+    }
+
+    #[test]
+    fn stack_containers() {
+        let mut vector = StackVector::<String, 4>::new();
+        vector.push("a".to_string());
+        vector.push("b".to_string());
+        round_trip(vector, 4 + 2 * (4 + 1));
+        // Same layout as a Vec, so the two are interchangeable
+        assert_eq!(
+            to_bytes(&StackVector::<u8, 3>::from_array([1, 2, 3])).unwrap(),
+            to_bytes(&vec![1u8, 2, 3]).unwrap()
+        );
+
+        // A queue that wrapped around is still written front to back
+        let mut queue = StackQueue::<u8, 3>::from_array([1, 2, 3]);
+        queue.pop_front();
+        queue.push_back(4).unwrap();
+        let bytes = to_bytes(&queue).unwrap();
+        assert_eq!(bytes, to_bytes(&vec![2u8, 3, 4]).unwrap());
+        let decoded: StackQueue<u8, 3> = from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        let too_many = to_bytes(&vec![1u8, 2, 3, 4]).unwrap();
+        assert!(matches!(
+            from_bytes::<StackVector<u8, 3>>(&too_many).unwrap_err(),
+            DecodeError::CapacityExceeded {
+                declared: 4,
+                capacity: 3
+            }
+        ));
+        assert!(matches!(
+            from_bytes::<StackQueue<u8, 3>>(&too_many).unwrap_err(),
+            DecodeError::CapacityExceeded {
+                declared: 4,
+                capacity: 3
+            }
+        ));
+    }
+
+    #[test]
+    fn user_record_round_trips() {
+        // Mirrors `User` from serialization_json.rs
+        #[derive(Serializable, Debug, PartialEq)]
+        struct User {
+            id: u32,
+            name: String,
+            email: String,
+            is_active: bool,
+            preferences: HashMap<String, String>,
+        }
+
+        let user = User {
+            id: 1,
+            name: "John Doe".to_string(),
+            email: "johndoes@example.com".to_string(),
+            is_active: true,
+            preferences: HashMap::from([
+                ("theme".to_string(), "dark".to_string()),
+                ("language".to_string(), "en".to_string()),
+            ]),
+        };
+        let encoded_len = 4 + (4 + 8) + (4 + 20) + 1 + 4 + (4 + 5 + 4 + 4) + (4 + 8 + 4 + 2);
+        round_trip(user, encoded_len);
+    }
+
+    #[test]
+    fn streams_several_values_through_one_cursor() {
+        let mut serializer = Serializer::new(Vec::new());
+        serializer.serialize(&7u8).unwrap();
+        serializer.serialize(&"ab".to_string()).unwrap();
+        serializer.serialize(&vec![1.0f64]).unwrap();
+        assert_eq!(serializer.position(), 1 + 6 + 12);
+        let bytes = serializer.into_inner();
+
+        let mut deserializer = Deserializer::new(Cursor::new(bytes));
+        assert_eq!(deserializer.deserialize::<u8>().unwrap(), 7);
+        assert_eq!(deserializer.deserialize::<String>().unwrap(), "ab");
+        assert_eq!(deserializer.position(), 7);
+        assert_eq!(deserializer.deserialize::<Vec<f64>>().unwrap(), vec![1.0]);
+        let error = deserializer.deserialize::<u8>().unwrap_err();
+        assert!(matches!(error, DecodeError::UnexpectedEof { offset: 19 }));
+    }
+
+    #[test]
+    fn writer_and_reader_helpers() {
+        let mut buffer = Vec::new();
+        to_writer(&mut buffer, &vec![10u32, 20]).unwrap();
+        assert_eq!(
+            from_reader::<Vec<u32>, _>(buffer.as_slice()).unwrap(),
+            vec![10, 20]
+        );
+    }
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        // Header, element count, string length, then the string bytes
+        let bytes = to_bytes(&vec!["abc".to_string()]).unwrap();
+        assert_eq!(bytes.len(), HEADER_LEN + 4 + 4 + 3);
+        for len in 0..bytes.len() {
+            let error = from_bytes::<Vec<String>>(&bytes[..len]).unwrap_err();
+            match len {
+                0..=3 => assert!(matches!(error, DecodeError::UnexpectedEof { offset: 0 })),
+                4..=5 => assert!(matches!(error, DecodeError::UnexpectedEof { offset: 4 })),
+                6..=9 => assert!(matches!(error, DecodeError::UnexpectedEof { offset: 6 })),
+                10 => assert!(matches!(
+                    error,
+                    DecodeError::CountMismatch {
+                        declared: 1,
+                        read: 0
+                    }
+                )),
+                11..=13 => assert!(matches!(error, DecodeError::UnexpectedEof { offset: 10 })),
+                _ => match error {
+                    DecodeError::LengthMismatch {
+                        declared,
+                        available,
+                    } => assert_eq!((declared, available), (3, len - 14)),
+                    error => panic!("unexpected error {:?}", error),
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn count_mismatch_reports_elements_read() {
+        let mut bytes = to_bytes(&vec![1u16, 2, 3]).unwrap();
+        bytes.truncate(bytes.len() - 2);
+        let error = from_bytes::<Vec<u16>>(&bytes).unwrap_err();
+        assert!(matches!(
+            error,
+            DecodeError::CountMismatch {
+                declared: 3,
+                read: 2
+            }
+        ));
+
+        // A cut inside an element is still an unexpected end of that element
+        bytes.pop();
+        let error = from_bytes::<Vec<u16>>(&bytes).unwrap_err();
+        assert!(matches!(error, DecodeError::UnexpectedEof { offset: 12 }));
+    }
+
+    #[test]
+    fn trailing_bytes_are_rejected() {
+        let mut bytes = to_bytes(&5u32).unwrap();
+        bytes.extend([0, 0]);
+        let error = from_bytes::<u32>(&bytes).unwrap_err();
+        assert!(matches!(error, DecodeError::TrailingBytes { count: 2 }));
+    }
+
+    #[test]
+    fn invalid_utf8_is_rejected() {
+        let mut bytes = to_bytes(&vec!["ok".to_string(), "ab".to_string()]).unwrap();
+        let offset = bytes.len() - 2;
+        bytes[offset] = 0xff;
+        let error = from_bytes::<Vec<String>>(&bytes).unwrap_err();
+        assert!(matches!(error, DecodeError::InvalidUtf8 { offset: o } if o == offset));
+    }
+
+    #[test]
+    fn huge_declared_length_fails_without_allocating_it() {
+        let mut bytes = to_bytes(&0u32).unwrap();
+        bytes[HEADER_LEN..].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            from_bytes::<String>(&bytes).unwrap_err(),
+            DecodeError::LengthMismatch {
+                declared,
+                available: 0
+            } if declared == u32::MAX as usize
+        ));
+        assert!(matches!(
+            from_bytes::<Vec<u64>>(&bytes).unwrap_err(),
+            DecodeError::CountMismatch { read: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn reader_failures_are_passed_through() {
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disconnected"))
+            }
+        }
+
+        let error = from_reader::<u8, _>(Broken).unwrap_err();
+        assert!(matches!(&error, DecodeError::Io(e) if e.to_string() == "disconnected"));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn header_and_little_endian_layout() {
+        let bytes = to_bytes(&vec![0x0102u16]).unwrap();
+        assert_eq!(&bytes[..4], b"LRSF");
+        assert_eq!(bytes[4], VERSION);
+        assert_eq!(bytes[5], 0);
+        assert_eq!(&bytes[HEADER_LEN..], &[1, 0, 0, 0, 0x02, 0x01]);
+    }
+
+    #[test]
+    fn big_endian_is_selectable() {
+        let mut serializer = Serializer::with_endian(Vec::new(), Endian::Big);
+        serializer.write_header().unwrap();
+        serializer.serialize(&vec![0x0102u16]).unwrap();
+        serializer.serialize(&-2i32).unwrap();
+        serializer.serialize(&1.0f32).unwrap();
+        let bytes = serializer.into_inner();
+        assert_eq!(bytes[5], 1);
+        assert_eq!(
+            &bytes[HEADER_LEN..],
+            &[0, 0, 0, 1, 0x01, 0x02, 0xff, 0xff, 0xff, 0xfe, 0x3f, 0x80, 0, 0]
+        );
+
+        // The header tells the reader which byte order to use
+        let mut deserializer = Deserializer::new(bytes.as_slice());
+        deserializer.read_header().unwrap();
+        assert_eq!(deserializer.endian(), Endian::Big);
+        assert_eq!(
+            deserializer.deserialize::<Vec<u16>>().unwrap(),
+            vec![0x0102]
+        );
+        assert_eq!(deserializer.deserialize::<i32>().unwrap(), -2);
+        assert_eq!(deserializer.deserialize::<f32>().unwrap(), 1.0);
+    }
+
+    #[test]
+    fn bad_headers_are_rejected() {
+        let bytes = to_bytes(&1u8).unwrap();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        let mut bad_version = bytes.clone();
+        bad_version[4] = VERSION + 1;
+        let mut bad_endian = bytes.clone();
+        bad_endian[5] = 2;
+
+        assert!(matches!(
+            from_bytes::<u8>(&bad_magic).unwrap_err(),
+            DecodeError::InvalidMagic
+        ));
+        assert!(matches!(
+            from_bytes::<u8>(&bad_version).unwrap_err(),
+            DecodeError::UnsupportedVersion(v) if v == VERSION + 1
+        ));
+        assert!(matches!(
+            from_bytes::<u8>(&bad_endian).unwrap_err(),
+            DecodeError::InvalidEndian(2)
+        ));
+        assert!(matches!(
+            from_bytes::<u8>(&[1]).unwrap_err(),
+            DecodeError::UnexpectedEof { offset: 0 }
+        ));
+    }
+
+    /// Decodes `bytes` as a handful of types; any error is fine, a panic is not.
+    fn decode_all(bytes: &[u8]) {
+        let _ = from_bytes::<u8>(bytes);
+        let _ = from_bytes::<u64>(bytes);
+        let _ = from_bytes::<f64>(bytes);
+        let _ = from_bytes::<String>(bytes);
+        let _ = from_bytes::<Vec<i32>>(bytes);
+        let _ = from_bytes::<Vec<String>>(bytes);
+        let _ = from_bytes::<Vec<Vec<u16>>>(bytes);
+        let _ = from_bytes::<(bool, char, Option<u8>)>(bytes);
+        let _ = from_bytes::<[Box<String>; 2]>(bytes);
+        let _ = from_bytes::<HashMap<String, Vec<u8>>>(bytes);
+        let _ = from_bytes::<BTreeMap<u16, Option<String>>>(bytes);
+        let _ = from_bytes::<HashSet<u32>>(bytes);
+        let _ = from_bytes::<StackVector<String, 4>>(bytes);
+        let _ = from_bytes::<StackQueue<u16, 4>>(bytes);
+        let _ = from_reader::<Vec<String>, _>(bytes);
+    }
+
+    /// Linked list whose impl has no depth guard of its own, only `Option` and `Box` do.
+    #[derive(Debug, PartialEq)]
+    struct Node(Option<Box<Node>>);
+
+    impl Serializable for Node {
+        fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+            serializer.serialize(&self.0)
+        }
+
+        fn custom_deserialize<R: Read>(
+            deserializer: &mut Deserializer<R>,
+        ) -> Result<Self, DecodeError> {
+            Ok(Node(deserializer.deserialize()?))
+        }
+    }
+
+    #[test]
+    fn options_and_boxes_count_towards_the_depth_limit() {
+        round_trip(Node(Some(Box::new(Node(None)))), 1 + 1);
+
+        // Every byte is another `Some`, so a megabyte nests a million levels deep
+        let mut bytes = to_bytes(&Node(None)).unwrap();
+        bytes.pop();
+        bytes.resize(HEADER_LEN + 1_000_000, 1);
+        assert!(matches!(
+            from_bytes::<Node>(&bytes).unwrap_err(),
+            DecodeError::DepthLimitExceeded { offset } if offset == HEADER_LEN + DEFAULT_MAX_DEPTH / 2
+        ));
+    }
+
+    /// Tree whose only depth guard is the one in the `Vec` impl.
+    #[derive(Debug, PartialEq)]
+    struct Tree(Vec<Tree>);
+
+    impl Serializable for Tree {
+        fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+            serializer.serialize(&self.0)
+        }
+
+        fn custom_deserialize<R: Read>(
+            deserializer: &mut Deserializer<R>,
+        ) -> Result<Self, DecodeError> {
+            Ok(Tree(deserializer.deserialize()?))
+        }
+    }
+
+    fn tree_of_depth(depth: usize) -> Tree {
+        (1..depth).fold(Tree(Vec::new()), |tree, _| Tree(vec![tree]))
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected_not_a_stack_overflow() {
+        // Exactly at the limit still decodes
+        round_trip(tree_of_depth(DEFAULT_MAX_DEPTH), 4 * DEFAULT_MAX_DEPTH);
+        let bytes = to_bytes(&tree_of_depth(DEFAULT_MAX_DEPTH + 1)).unwrap();
+        assert!(matches!(
+            from_bytes::<Tree>(&bytes).unwrap_err(),
+            DecodeError::DepthLimitExceeded { offset } if offset == HEADER_LEN + 4 * DEFAULT_MAX_DEPTH
+        ));
+
+        // A count of one, over and over, for 4 MB
+        let mut bytes = to_bytes(&Tree(Vec::new())).unwrap();
+        bytes.truncate(HEADER_LEN);
+        for _ in 0..1_000_000 {
+            bytes.extend(1u32.to_le_bytes());
+        }
+        assert!(matches!(
+            from_bytes::<Tree>(&bytes).unwrap_err(),
+            DecodeError::DepthLimitExceeded { .. }
+        ));
+
+        // The limit can be raised for trusted input
+        let bytes = to_bytes(&tree_of_depth(DEFAULT_MAX_DEPTH + 1)).unwrap();
+        let mut deserializer = Deserializer::new(bytes.as_slice());
+        deserializer.read_header().unwrap();
+        deserializer.set_max_depth(DEFAULT_MAX_DEPTH + 1);
+        assert_eq!(
+            deserializer.deserialize::<Tree>().unwrap(),
+            tree_of_depth(DEFAULT_MAX_DEPTH + 1)
+        );
+    }
+
+    #[test]
+    fn random_input_never_panics() {
+        let mut rng = StdRng::seed_from_u64(23);
+        for _ in 0..2000 {
+            let mut bytes = vec![0u8; rng.gen_range(0..48)];
+            rng.fill(bytes.as_mut_slice());
+            // Give half of the inputs a valid header so the value decoders get exercised
+            if rng.gen_bool(0.5) && bytes.len() >= HEADER_LEN {
+                bytes[..4].copy_from_slice(&MAGIC);
+                bytes[4] = VERSION;
+                bytes[5] = rng.gen_range(0..2);
+            }
+            decode_all(&bytes);
+        }
+    }
+
+    #[test]
+    fn mutated_input_never_panics() {
+        let mut rng = StdRng::seed_from_u64(24);
+        let value = vec![vec!["lidar".to_string(), "Grüße".to_string()], vec![]];
+        let original = to_bytes(&value).unwrap();
+        for _ in 0..2000 {
+            let mut bytes = original.clone();
+            for _ in 0..rng.gen_range(1..4) {
+                let index = rng.gen_range(0..bytes.len());
+                match rng.gen_range(0..3) {
+                    0 => bytes[index] = rng.gen(),
+                    1 => bytes.truncate(index),
+                    _ => bytes.insert(index, rng.gen()),
+                }
+                if bytes.is_empty() {
+                    break;
+                }
+            }
+            decode_all(&bytes);
+            let _ = from_bytes::<Vec<Vec<String>>>(&bytes);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use challenges::small_vector::SmallVector;
+
+fn main() {
+    let mut small_vector = SmallVector::<i32, 4>::new();
+
+    for i in 0..4 {
+        small_vector.push(i);
+    }
+    println!("{:?}, spilled: {}", small_vector, small_vector.spilled());
+
+    small_vector.push(4);
+    println!("{:?}, spilled: {}", small_vector, small_vector.spilled());
+
+    small_vector.truncate(2);
+    small_vector.shrink_to_inline();
+    println!("{:?}, spilled: {}", small_vector, small_vector.spilled());
+}
+
+#[cfg(test)]
+#[path = "common/drop_counter.rs"]
+mod drop_counter;
+
+#[cfg(test)]
+mod tests {
+    use crate::drop_counter::DropCounter;
+    use challenges::small_vector::SmallVector;
+    use std::cell::Cell;
+
+    #[test]
+    fn stays_inline_within_capacity() {
+        let mut vector = SmallVector::<i32, 3>::new();
+        vector.push(1);
+        vector.push(2);
+        vector.push(3);
+        assert!(!vector.spilled());
+        assert_eq!(vector.capacity(), 3);
+        assert_eq!(&vector[..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn spills_past_capacity() {
+        let mut vector = SmallVector::<i32, 2>::new();
+        vector.extend([1, 2]);
+        vector.push(3);
+        assert!(vector.spilled());
+        assert!(vector.capacity() >= 3);
+        assert_eq!(&vector[..], &[1, 2, 3]);
+        assert_eq!(vector.pop(), Some(3));
+        assert_eq!(vector[1], 2);
+    }
+
+    #[test]
+    fn insert_spills_and_keeps_order() {
+        let mut vector = SmallVector::<i32, 2>::new();
+        vector.insert(0, 3);
+        vector.insert(0, 1);
+        vector.insert(1, 2);
+        assert!(vector.spilled());
+        assert_eq!(&vector[..], &[1, 2, 3]);
+        assert_eq!(vector.remove(0), 1);
+        assert_eq!(vector.swap_remove(0), 2);
+        assert_eq!(&vector[..], &[3]);
+    }
+
+    #[test]
+    fn shrink_to_inline_only_when_it_fits() {
+        let mut vector: SmallVector<i32, 2> = (0..4).collect();
+        assert!(vector.spilled());
+        assert!(!vector.shrink_to_inline());
+        vector.retain(|&x| x % 2 == 0);
+        assert!(vector.shrink_to_inline());
+        assert!(!vector.spilled());
+        assert_eq!(&vector[..], &[0, 2]);
+    }
+
+    #[test]
+    fn elements_dropped_once_across_spill() {
+        let drops = Cell::new(0);
+        let mut vector = SmallVector::<DropCounter, 2>::new();
+        for _ in 0..5 {
+            vector.push(DropCounter::new(0, &drops));
+        }
+        assert_eq!(drops.get(), 0);
+        vector.truncate(1);
+        assert_eq!(drops.get(), 4);
+        vector.shrink_to_inline();
+        assert_eq!(drops.get(), 4);
+        drop(vector);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn into_iter_inline_and_heap() {
+        let inline: SmallVector<i32, 4> = (1..=3).collect();
+        assert_eq!(inline.into_iter().rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+        let heap: SmallVector<i32, 2> = (1..=3).collect();
+        let mut iter = heap.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.as_slice(), &[2, 3]);
+    }
+
+    #[test]
+    fn slice_api_and_equality() {
+        let mut a: SmallVector<i32, 2> = [3, 1, 2].into_iter().collect();
+        a.sort();
+        let b = SmallVector::<i32, 2>::from(vec![1, 2, 3]);
+        assert_eq!(a, b);
+        for x in &mut a {
+            *x *= 2;
+        }
+        assert_eq!((&a).into_iter().sum::<i32>(), 12);
+        assert_eq!(Vec::from(a.clone()), vec![2, 4, 6]);
+    }
+}
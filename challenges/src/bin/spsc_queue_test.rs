@@ -0,0 +1,201 @@
+use std::thread;
+
+use challenges::spsc_queue::SpscQueue;
+
+#[derive(Clone, Debug)]
+struct LidarFrame {
+    id: u32,
+    points: Vec<f32>,
+}
+
+fn main() {
+    let mut queue = SpscQueue::<LidarFrame, 4>::new();
+    let (mut producer, mut consumer) = queue.split();
+
+    thread::scope(|s| {
+        s.spawn(move || {
+            for id in 0..10 {
+                let mut frame = LidarFrame {
+                    id,
+                    points: vec![id as f32; 3],
+                };
+                // Spin until the processing thread frees a slot
+                while let Err(e) = producer.push(frame) {
+                    frame = e.item();
+                    thread::yield_now();
+                }
+                println!("Captured frame {}", id);
+            }
+        });
+
+        s.spawn(move || {
+            let mut received = 0;
+            while received < 10 {
+                match consumer.pop() {
+                    Some(frame) => {
+                        println!("Processed frame {} with {:?}", frame.id, frame.points);
+                        received += 1;
+                    }
+                    None => thread::yield_now(),
+                }
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use challenges::spsc_queue::SpscQueue;
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    #[test]
+    fn push_and_pop_single_thread() {
+        let mut queue = SpscQueue::<i32, 2>::new();
+        let (mut producer, mut consumer) = queue.split();
+        assert!(consumer.pop().is_none());
+        producer.push(1).unwrap();
+        producer.push(2).unwrap();
+        assert_eq!(producer.push(3).unwrap_err().item(), 3);
+        assert!(producer.is_full());
+        assert_eq!(consumer.peek(), Some(&1));
+        assert_eq!(consumer.pop(), Some(1));
+        producer.push(3).unwrap();
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(consumer.pop(), Some(3));
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn wraps_around_many_times() {
+        let mut queue = SpscQueue::<usize, 3>::new();
+        let (mut producer, mut consumer) = queue.split();
+        for i in 0..100 {
+            producer.push(i).unwrap();
+            producer.push(i + 1000).unwrap();
+            assert_eq!(consumer.pop(), Some(i));
+            assert_eq!(consumer.pop(), Some(i + 1000));
+        }
+    }
+
+    #[test]
+    fn push_slice_and_pop_into() {
+        let mut queue = SpscQueue::<i32, 4>::new();
+        let (mut producer, mut consumer) = queue.split();
+        assert_eq!(producer.push_slice(&[1, 2, 3, 4, 5, 6]), 4);
+        let mut out = [0; 3];
+        assert_eq!(consumer.pop_into(&mut out), 3);
+        assert_eq!(out, [1, 2, 3]);
+        assert_eq!(producer.push_slice(&[5, 6]), 2);
+        assert_eq!(consumer.pop_into(&mut out), 3);
+        assert_eq!(out, [4, 5, 6]);
+        assert_eq!(consumer.pop_into(&mut out), 0);
+    }
+
+    #[test]
+    fn drop_releases_remaining() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counted;
+
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let mut queue = SpscQueue::<Counted, 4>::new();
+        {
+            let (mut producer, mut consumer) = queue.split();
+            for _ in 0..3 {
+                producer.push(Counted).ok().unwrap();
+            }
+            drop(consumer.pop());
+        }
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+        drop(queue);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn stress_preserves_order_across_threads() {
+        const ITEMS: usize = 200_000;
+        let mut queue = SpscQueue::<usize, 7>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..ITEMS {
+                    let mut item = i;
+                    while let Err(e) = producer.push(item) {
+                        item = e.item();
+                        thread::yield_now();
+                    }
+                }
+            });
+
+            s.spawn(move || {
+                let mut expected = 0;
+                while expected < ITEMS {
+                    match consumer.pop() {
+                        Some(item) => {
+                            assert_eq!(item, expected);
+                            expected += 1;
+                        }
+                        None => thread::yield_now(),
+                    }
+                }
+            });
+        });
+    }
+
+    #[test]
+    fn stress_batches_across_threads() {
+        const BATCHES: usize = 20_000;
+        let mut queue = SpscQueue::<u64, 16>::new();
+        let (mut producer, mut consumer) = queue.split();
+
+        thread::scope(|s| {
+            s.spawn(move || {
+                let mut next = 0u64;
+                let total = (BATCHES * 5) as u64;
+                while next < total {
+                    let batch: Vec<u64> = (next..(next + 5).min(total)).collect();
+                    let pushed = producer.push_slice(&batch) as u64;
+                    if pushed == 0 {
+                        thread::yield_now();
+                    }
+                    next += pushed;
+                }
+            });
+
+            s.spawn(move || {
+                let mut expected = 0u64;
+                let mut buffer = [0u64; 6];
+                while expected < (BATCHES * 5) as u64 {
+                    let count = consumer.pop_into(&mut buffer);
+                    if count == 0 {
+                        thread::yield_now();
+                    }
+                    for &item in &buffer[..count] {
+                        assert_eq!(item, expected);
+                        expected += 1;
+                    }
+                }
+            });
+        });
+    }
+
+    #[test]
+    fn handles_move_non_sync_items_across_threads() {
+        let mut queue = SpscQueue::<Cell<u32>, 4>::new();
+        let (mut producer, mut consumer) = queue.split();
+        thread::scope(|s| {
+            s.spawn(move || producer.push(Cell::new(7)).unwrap())
+                .join()
+                .unwrap();
+            s.spawn(move || assert_eq!(consumer.pop().map(Cell::into_inner), Some(7)));
+        });
+    }
+}
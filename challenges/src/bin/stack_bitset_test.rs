@@ -0,0 +1,129 @@
+use challenges::stack_bitset;
+
+fn main() {
+    // Occupancy of a 10 x 10 voxel slice
+    let mut occupied = <stack_bitset!(100)>::new();
+    for (x, y) in [(1, 2), (3, 4), (9, 9)] {
+        occupied.set(y * 10 + x);
+    }
+    println!(
+        "Occupied voxels: {:?}, count: {}",
+        occupied,
+        occupied.count_ones()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use challenges::stack_bitset;
+    use challenges::stack_bitset::{words_for, StackBitSet};
+
+    type Bits130 = stack_bitset!(130);
+
+    #[test]
+    fn set_clear_toggle_test() {
+        let mut bits = Bits130::new();
+        assert!(bits.is_empty());
+        bits.set(0);
+        bits.set(64);
+        bits.set(129);
+        assert!(bits.test(0) && bits.test(64) && bits.test(129));
+        assert!(!bits.test(1));
+        bits.clear(64);
+        assert!(!bits.test(64));
+        bits.toggle(1);
+        bits.toggle(0);
+        assert!(bits.test(1) && !bits.test(0));
+        assert_eq!(bits.count_ones(), 2);
+        assert_eq!(bits.count_zeros(), 128);
+        assert_eq!(bits.capacity(), 130);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn set_out_of_range_panics() {
+        let mut bits = Bits130::new();
+        bits.set(130);
+    }
+
+    #[test]
+    fn set_all_stays_within_capacity() {
+        let mut bits = Bits130::new();
+        bits.set_all();
+        assert_eq!(bits.count_ones(), 130);
+        assert_eq!(bits.as_words()[2], 0b11);
+        bits.clear_all();
+        assert!(bits.is_empty());
+    }
+
+    #[test]
+    fn iter_ones_is_ascending() {
+        let bits: Bits130 = [129, 3, 64, 63, 0].into_iter().collect();
+        assert_eq!(
+            bits.iter_ones().collect::<Vec<_>>(),
+            vec![0, 3, 63, 64, 129]
+        );
+        assert_eq!(Bits130::new().iter_ones().next(), None);
+        assert_eq!(format!("{:?}", bits), "{0, 3, 63, 64, 129}");
+    }
+
+    #[test]
+    fn set_operators() {
+        let a: Bits130 = [1, 2, 3, 100].into_iter().collect();
+        let b: Bits130 = [3, 4, 100, 129].into_iter().collect();
+        assert_eq!(
+            (a | b).iter_ones().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 100, 129]
+        );
+        assert_eq!((a & b).iter_ones().collect::<Vec<_>>(), vec![3, 100]);
+        assert_eq!((a - b).iter_ones().collect::<Vec<_>>(), vec![1, 2]);
+        let mut c = a;
+        c -= a;
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn rank_and_select() {
+        let bits: Bits130 = [2, 5, 64, 70, 129].into_iter().collect();
+        assert_eq!(bits.rank(0), 0);
+        assert_eq!(bits.rank(3), 1);
+        assert_eq!(bits.rank(64), 2);
+        assert_eq!(bits.rank(65), 3);
+        assert_eq!(bits.rank(130), 5);
+        for (n, index) in bits.iter_ones().enumerate() {
+            assert_eq!(bits.select(n), Some(index));
+            assert_eq!(bits.rank(index), n);
+        }
+        assert_eq!(bits.select(5), None);
+    }
+
+    #[test]
+    fn exact_word_multiple() {
+        let mut bits = StackBitSet::<64, { words_for(64) }>::new();
+        bits.set_all();
+        assert_eq!(bits.count_ones(), 64);
+        assert_eq!(bits.rank(64), 64);
+        assert_eq!(bits.select(63), Some(63));
+    }
+
+    #[test]
+    fn macro_names_the_same_type() {
+        let explicit = StackBitSet::<70, { words_for(70) }>::from_iter([1, 69]);
+        let named: stack_bitset!(70) = explicit;
+        assert_eq!(named.as_words().len(), 2);
+        assert_eq!(named, explicit);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let bits: Bits130 = [0, 65, 129].into_iter().collect();
+        let json = serde_json::to_string(&bits).unwrap();
+        assert_eq!(json, "[1,2,2]");
+        let decoded: Bits130 = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, bits);
+        assert!(serde_json::from_str::<Bits130>("[1,2]").is_err());
+        assert!(serde_json::from_str::<Bits130>("[1,2,2,0]").is_err());
+        assert!(serde_json::from_str::<Bits130>("[1,2,4]").is_err());
+    }
+}
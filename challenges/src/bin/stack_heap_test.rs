@@ -0,0 +1,160 @@
+use challenges::stack_heap::{MinHeap, StackHeap};
+
+fn main() {
+    let mut heap = StackHeap::<i32, 8>::new();
+    heap.extend([5, 1, 8, 3, 9, 2]);
+    println!("Top: {:?}, len: {}", heap.peek(), heap.len());
+
+    // Keep the three closest ranges out of a stream of LiDAR returns
+    let mut closest = StackHeap::<u32, 3, MinHeap>::new_min();
+    for range in [42, 7, 19, 3, 88, 11] {
+        if let Some(dropped) = closest.push_top_k(range) {
+            println!("Dropped {}", dropped);
+        }
+    }
+    println!("Closest: {:?}", closest.into_sorted_vec());
+}
+
+#[cfg(test)]
+#[path = "common/drop_counter.rs"]
+mod drop_counter;
+
+#[cfg(test)]
+mod tests {
+    use crate::drop_counter::DropCounter;
+    use challenges::stack_heap::{MinHeap, PeekMut, StackHeap};
+    use challenges::stack_vector::CapacityError;
+    use std::cell::Cell;
+
+    #[test]
+    fn pops_in_descending_order() {
+        let mut heap = StackHeap::<i32, 8>::new();
+        heap.extend([4, 1, 7, 3, 9, 2, 9]);
+        assert_eq!(heap.peek(), Some(&9));
+        let mut popped = Vec::new();
+        while let Some(item) = heap.pop() {
+            popped.push(item);
+        }
+        assert_eq!(popped, vec![9, 9, 7, 4, 3, 2, 1]);
+        assert_eq!(heap.pop(), None);
+        assert_eq!(heap.peek(), None);
+    }
+
+    #[test]
+    fn min_heap_pops_smallest_first() {
+        let mut heap: StackHeap<i32, 5, MinHeap> = [5, 3, 8, 1, 4].into_iter().collect();
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.peek(), Some(&4));
+    }
+
+    #[test]
+    fn try_push_returns_item_when_full() {
+        let mut heap = StackHeap::<i32, 2>::new();
+        assert_eq!(heap.try_push(1), Ok(()));
+        assert_eq!(heap.try_push(2), Ok(()));
+        assert!(heap.is_full());
+        assert_eq!(heap.try_push(3), Err(CapacityError::new(3)));
+        assert_eq!(heap.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity exceeded")]
+    fn push_panics_when_full() {
+        let mut heap = StackHeap::<i32, 1>::new();
+        heap.push(1);
+        heap.push(2);
+    }
+
+    #[test]
+    fn custom_comparator() {
+        let by_distance = |a: &(i32, i32), b: &(i32, i32)| {
+            let norm = |p: &(i32, i32)| p.0 * p.0 + p.1 * p.1;
+            norm(b).cmp(&norm(a))
+        };
+        let mut heap = StackHeap::<(i32, i32), 4, _>::with_comparator(by_distance);
+        heap.extend([(3, 4), (1, 1), (0, 2), (5, 0)]);
+        assert_eq!(heap.pop(), Some((1, 1)));
+        assert_eq!(heap.pop(), Some((0, 2)));
+    }
+
+    #[test]
+    fn peek_mut_sifts_on_drop() {
+        let mut heap = StackHeap::<i32, 4>::new();
+        heap.extend([10, 6, 8, 2]);
+        *heap.peek_mut().unwrap() = 1;
+        assert_eq!(heap.peek(), Some(&8));
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 6, 8]);
+    }
+
+    #[test]
+    fn peek_mut_pop_removes_top() {
+        let mut heap = StackHeap::<i32, 4>::new();
+        heap.extend([3, 5, 1]);
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(PeekMut::pop(top), 5);
+        assert_eq!(heap.peek(), Some(&3));
+        assert!(StackHeap::<i32, 1>::new().peek_mut().is_none());
+    }
+
+    #[test]
+    fn into_sorted_vec_is_ascending() {
+        let heap: StackHeap<i32, 6> = [4, 6, 1, 5, 2, 3].into_iter().collect();
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 3, 4, 5, 6]);
+
+        let heap: StackHeap<i32, 3, MinHeap> = [2, 3, 1].into_iter().collect();
+        assert_eq!(heap.into_sorted_vec(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn push_top_k_keeps_best_elements() {
+        let mut heap = StackHeap::<i32, 3>::new();
+        assert_eq!(heap.push_top_k(5), None);
+        assert_eq!(heap.push_top_k(1), None);
+        assert_eq!(heap.push_top_k(3), None);
+        assert_eq!(heap.push_top_k(0), Some(0));
+        assert_eq!(heap.push_top_k(4), Some(1));
+        assert_eq!(heap.push_top_k(9), Some(3));
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(heap.into_sorted_vec(), vec![4, 5, 9]);
+    }
+
+    #[test]
+    fn push_top_k_with_zero_capacity_returns_item() {
+        let mut heap = StackHeap::<String, 0>::new();
+        assert_eq!(heap.push_top_k("a".to_string()), Some("a".to_string()));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn push_top_k_matches_sorting() {
+        let values = [17, 3, 99, 42, 8, 56, 23, 71, 5, 64, 30, 88, 12];
+        let mut heap = StackHeap::<i32, 5, MinHeap>::new_min();
+        for value in values {
+            heap.push_top_k(value);
+        }
+        let mut expected = values.to_vec();
+        expected.sort();
+        expected.truncate(5);
+        expected.reverse();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn drops_every_element_once() {
+        let drops = Cell::new(0);
+        let by_value = |a: &DropCounter, b: &DropCounter| a.value.cmp(&b.value);
+        {
+            let mut heap = StackHeap::<DropCounter, 3, _>::with_comparator(by_value);
+            for value in 0..3 {
+                heap.push(DropCounter::new(value, &drops));
+            }
+            let evicted = heap.push_top_k(DropCounter::new(10, &drops));
+            assert_eq!(evicted.map(|e| e.value), Some(0));
+            assert_eq!(drops.get(), 1);
+            assert_eq!(heap.pop().map(|e| e.value), Some(10));
+            assert_eq!(drops.get(), 2);
+        }
+        assert_eq!(drops.get(), 4);
+    }
+}
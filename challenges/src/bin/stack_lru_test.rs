@@ -0,0 +1,153 @@
+use challenges::stack_lru::{Displaced, StackLru};
+
+fn main() {
+    let mut frames = StackLru::<u32, &str, 2>::new();
+    frames.put(1, "frame 1");
+    frames.put(2, "frame 2");
+    frames.get(&1);
+    if let Some(Displaced::Evicted(id, _)) = frames.put(3, "frame 3") {
+        println!("Evicted frame {}", id);
+    }
+    frames.get(&2);
+    println!(
+        "{:?}, hits: {}, misses: {}",
+        frames,
+        frames.hits(),
+        frames.misses()
+    );
+}
+
+#[cfg(test)]
+#[path = "common/drop_counter.rs"]
+mod drop_counter;
+
+#[cfg(test)]
+mod tests {
+    use crate::drop_counter::DropCounter;
+    use challenges::stack_lru::{Displaced, StackLru};
+    use std::cell::Cell;
+
+    fn keys<const N: usize>(lru: &StackLru<i32, i32, N>) -> Vec<i32> {
+        lru.iter().map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn put_evicts_least_recently_used() {
+        let mut lru = StackLru::<i32, i32, 3>::new();
+        assert_eq!(lru.put(1, 10), None);
+        assert_eq!(lru.put(2, 20), None);
+        assert_eq!(lru.put(3, 30), None);
+        assert!(lru.is_full());
+        assert_eq!(lru.put(4, 40), Some(Displaced::Evicted(1, 10)));
+        assert_eq!(keys(&lru), vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn get_promotes_and_peek_does_not() {
+        let mut lru = StackLru::<i32, i32, 3>::new();
+        for key in 1..=3 {
+            lru.put(key, key * 10);
+        }
+        assert_eq!(lru.peek(&1), Some(&10));
+        assert_eq!(keys(&lru), vec![3, 2, 1]);
+        assert_eq!(lru.get(&1), Some(&10));
+        assert_eq!(keys(&lru), vec![1, 3, 2]);
+        assert_eq!(lru.put(4, 40), Some(Displaced::Evicted(2, 20)));
+        *lru.get_mut(&3).unwrap() += 1;
+        assert_eq!(lru.peek(&3), Some(&31));
+        assert_eq!(keys(&lru), vec![3, 4, 1]);
+    }
+
+    #[test]
+    fn put_existing_key_replaces_and_promotes() {
+        let mut lru = StackLru::<String, i32, 2>::new();
+        lru.put("a".to_string(), 1);
+        lru.put("b".to_string(), 2);
+        assert_eq!(lru.put("a".to_string(), 3), Some(Displaced::Replaced(1)));
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.peek_lru(), Some((&"b".to_string(), &2)));
+        assert_eq!(lru.peek("a"), Some(&3));
+    }
+
+    #[test]
+    fn put_tells_replacement_from_eviction() {
+        let mut lru = StackLru::<i32, &str, 2>::new();
+        assert_eq!(lru.put(1, "a"), None);
+        assert_eq!(lru.put(2, "b"), None);
+        // Updating a cached key never evicts, even when the cache is full
+        assert_eq!(lru.put(1, "c"), Some(Displaced::Replaced("a")));
+        assert_eq!(lru.len(), 2);
+        assert_eq!(lru.put(3, "d"), Some(Displaced::Evicted(2, "b")));
+        assert_eq!(lru.put(3, "e"), Some(Displaced::Replaced("d")));
+    }
+
+    #[test]
+    fn pop_lru_and_remove() {
+        let mut lru = StackLru::<i32, i32, 4>::new();
+        for key in 1..=4 {
+            lru.put(key, key);
+        }
+        assert_eq!(lru.pop_lru(), Some((1, 1)));
+        assert_eq!(lru.remove(&3), Some(3));
+        assert_eq!(lru.remove(&3), None);
+        assert_eq!(keys(&lru), vec![4, 2]);
+        lru.put(5, 5);
+        lru.put(6, 6);
+        assert_eq!(keys(&lru), vec![6, 5, 4, 2]);
+        assert_eq!(lru.pop_lru(), Some((2, 2)));
+        assert_eq!(lru.pop_lru(), Some((4, 4)));
+        assert_eq!(lru.pop_lru(), Some((5, 5)));
+        assert_eq!(lru.pop_lru(), Some((6, 6)));
+        assert_eq!(lru.pop_lru(), None);
+        assert!(lru.is_empty());
+    }
+
+    #[test]
+    fn hit_and_miss_counters() {
+        let mut lru = StackLru::<i32, i32, 2>::new();
+        lru.put(1, 1);
+        lru.get(&1);
+        lru.get(&2);
+        lru.get(&2);
+        lru.peek(&1);
+        assert_eq!((lru.hits(), lru.misses()), (1, 2));
+        lru.reset_stats();
+        assert_eq!((lru.hits(), lru.misses()), (0, 0));
+    }
+
+    #[test]
+    fn zero_capacity_rejects_everything() {
+        let mut lru = StackLru::<i32, i32, 0>::new();
+        assert_eq!(lru.put(1, 1), Some(Displaced::Evicted(1, 1)));
+        assert_eq!(lru.get(&1), None);
+        assert_eq!(lru.pop_lru(), None);
+    }
+
+    #[test]
+    fn clone_keeps_order() {
+        let mut lru = StackLru::<i32, i32, 3>::new();
+        for key in 1..=3 {
+            lru.put(key, key);
+        }
+        lru.get(&2);
+        let mut clone = lru.clone();
+        assert_eq!(keys(&clone), keys(&lru));
+        assert_eq!(clone.hits(), 1);
+        assert_eq!(clone.put(4, 4), Some(Displaced::Evicted(1, 1)));
+    }
+
+    #[test]
+    fn drops_every_value_once() {
+        let drops = Cell::new(0);
+        {
+            let mut lru = StackLru::<i32, DropCounter, 2>::new();
+            lru.put(1, DropCounter::new(0, &drops));
+            lru.put(2, DropCounter::new(0, &drops));
+            drop(lru.put(3, DropCounter::new(0, &drops)));
+            assert_eq!(drops.get(), 1);
+            lru.remove(&2);
+            assert_eq!(drops.get(), 2);
+        }
+        assert_eq!(drops.get(), 3);
+    }
+}
@@ -0,0 +1,272 @@
+use challenges::stack_map::StackMap;
+
+fn main() {
+    let mut preferences = StackMap::<&str, &str, 8>::new();
+    preferences.insert("theme", "dark").unwrap();
+    preferences.insert("language", "en").unwrap();
+    *preferences.entry("theme").unwrap().or_insert("light") = "solarized";
+    println!("{:?}", preferences);
+
+    let mut full = StackMap::<u8, u8, 1>::new();
+    full.insert(1, 1).unwrap();
+    if let Err(e) = full.insert(2, 2) {
+        println!("Insert failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+#[path = "common/drop_counter.rs"]
+mod drop_counter;
+
+#[cfg(test)]
+mod tests {
+    use crate::drop_counter::DropCounter;
+    use challenges::stack_map::{Entry, StackMap};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    /// Sends every key to the slot given by its last written byte, so tests can
+    /// lay out clusters by hand.
+    #[derive(Default)]
+    struct SlotHasher(u64);
+
+    impl Hasher for SlotHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            if let Some(&byte) = bytes.last() {
+                self.0 = byte as u64;
+            }
+        }
+
+        fn write_u32(&mut self, value: u32) {
+            self.0 = (value % 100) as u64;
+        }
+    }
+
+    type Slotted<const N: usize> = StackMap<u32, u32, N, BuildHasherDefault<SlotHasher>>;
+
+    #[test]
+    fn insert_get_remove() {
+        let mut map = StackMap::<String, i32, 4>::new();
+        assert_eq!(map.insert("a".to_string(), 1).unwrap(), None);
+        assert_eq!(map.insert("b".to_string(), 2).unwrap(), None);
+        assert_eq!(map.insert("a".to_string(), 3).unwrap(), Some(1));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("a"), Some(&3));
+        assert_eq!(map["b"], 2);
+        assert!(map.contains_key("b"));
+        *map.get_mut("b").unwrap() += 10;
+        assert_eq!(map.remove("b"), Some(12));
+        assert_eq!(map.remove("b"), None);
+        assert_eq!(map.get("b"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn insert_returns_pair_when_full() {
+        let mut map = StackMap::<i32, i32, 2>::new();
+        map.insert(1, 10).unwrap();
+        map.insert(2, 20).unwrap();
+        assert!(map.is_full());
+        assert_eq!(map.insert(3, 30).unwrap_err().element(), (3, 30));
+        // Existing keys can still be updated
+        assert_eq!(map.insert(2, 21).unwrap(), Some(20));
+        assert_eq!(map.get(&3), None);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut map = StackMap::<i32, i32, 0>::new();
+        assert!(map.insert(1, 1).is_err());
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.remove(&1), None);
+    }
+
+    #[test]
+    fn removal_keeps_cluster_reachable() {
+        let mut map = Slotted::<8>::default();
+        // 10, 18 and 26 all want slot 2, 3 wants slot 3 and ends up behind them
+        for key in [10, 18, 26, 3] {
+            map.insert(key, key).unwrap();
+        }
+        assert_eq!(map.remove(&10), Some(10));
+        for key in [18, 26, 3] {
+            assert_eq!(map.get(&key), Some(&key));
+        }
+        assert_eq!(map.remove(&18), Some(18));
+        assert_eq!(map.remove(&26), Some(26));
+        assert_eq!(map.get(&3), Some(&3));
+    }
+
+    #[test]
+    fn removal_shifts_across_wrap_around() {
+        let mut map = Slotted::<4>::default();
+        // Everything wants slot 3, so the cluster wraps to slots 0 and 1
+        for key in [3, 7, 11] {
+            map.insert(key, key).unwrap();
+        }
+        assert_eq!(map.remove(&3), Some(3));
+        assert_eq!(map.get(&7), Some(&7));
+        assert_eq!(map.get(&11), Some(&11));
+        map.insert(15, 15).unwrap();
+        map.insert(19, 19).unwrap();
+        assert!(map.is_full());
+        assert_eq!(map.get(&19), Some(&19));
+    }
+
+    #[test]
+    fn full_table_lookup_of_missing_key_terminates() {
+        let mut map = Slotted::<3>::default();
+        for key in [0, 1, 2] {
+            map.insert(key, key).unwrap();
+        }
+        assert_eq!(map.get(&5), None);
+        assert!(map.entry(5).is_err());
+    }
+
+    #[test]
+    fn entry_api() {
+        let mut map = StackMap::<&str, i32, 4>::new();
+        *map.entry("a").unwrap().or_insert(0) += 1;
+        *map.entry("a").unwrap().or_insert(0) += 1;
+        *map.entry("b").unwrap().or_default() += 5;
+        map.entry("b").unwrap().and_modify(|v| *v *= 2).or_insert(0);
+        assert_eq!(map["a"], 2);
+        assert_eq!(map["b"], 10);
+
+        match map.entry("a").unwrap() {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &"a");
+                assert_eq!(entry.insert(7), 2);
+                assert_eq!(entry.remove(), 7);
+            }
+            Entry::Vacant(_) => panic!("expected an occupied entry"),
+        }
+        match map.entry("c").unwrap() {
+            Entry::Vacant(entry) => assert_eq!(entry.into_key(), "c"),
+            Entry::Occupied(_) => panic!("expected a vacant entry"),
+        }
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn entry_fails_only_for_new_keys_when_full() {
+        let mut map = StackMap::<i32, i32, 1>::new();
+        map.insert(1, 1).unwrap();
+        assert_eq!(*map.entry(1).unwrap().or_insert(0), 1);
+        assert_eq!(map.entry(2).err().map(|e| e.element()), Some(2));
+    }
+
+    #[test]
+    fn iteration() {
+        let mut map: StackMap<i32, i32, 8> = [(1, 10), (2, 20), (3, 30)].into();
+        let iter = map.iter();
+        assert_eq!(iter.len(), 3);
+        let mut entries: Vec<_> = iter.map(|(k, v)| (*k, *v)).collect();
+        entries.sort();
+        assert_eq!(entries, vec![(1, 10), (2, 20), (3, 30)]);
+
+        for (_, value) in &mut map {
+            *value += 1;
+        }
+        map.values_mut().for_each(|value| *value *= 2);
+        let mut keys: Vec<_> = map.keys().copied().collect();
+        keys.sort();
+        assert_eq!(keys, vec![1, 2, 3]);
+        assert_eq!(map.values().sum::<i32>(), 22 + 42 + 62);
+
+        let mut owned: Vec<_> = map.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, vec![(1, 22), (2, 42), (3, 62)]);
+    }
+
+    #[test]
+    fn retain_visits_each_entry_once() {
+        let mut map = Slotted::<8>::default();
+        // Clusters at slots 6, 7, 0, 1 and 2, 3 so removals shift across the wrap
+        for key in [6, 14, 22, 30, 2, 10] {
+            map.insert(key, 0).unwrap();
+        }
+        map.retain(|key, visits| {
+            *visits += 1;
+            key % 4 != 2
+        });
+        assert_eq!(map.len(), 0);
+
+        for key in [6, 14, 22, 30, 2, 10, 7] {
+            map.insert(key, 0).unwrap();
+        }
+        map.retain(|key, visits| {
+            *visits += 1;
+            *key == 30 || *key == 7
+        });
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&30), Some(&1));
+        assert_eq!(map.get(&7), Some(&1));
+    }
+
+    #[test]
+    fn matches_hash_map_under_random_operations() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut map = Slotted::<16>::default();
+        let mut reference = HashMap::new();
+        for _ in 0..10_000 {
+            let key = rng.gen_range(0..40);
+            match rng.gen_range(0..3) {
+                0 => {
+                    let expected = reference.get(&key).copied();
+                    let result = map.insert(key, key * 2);
+                    if expected.is_some() || reference.len() < 16 {
+                        assert_eq!(result.unwrap(), expected);
+                        reference.insert(key, key * 2);
+                    } else {
+                        assert!(result.is_err());
+                    }
+                }
+                1 => assert_eq!(map.remove(&key), reference.remove(&key)),
+                _ => assert_eq!(map.get(&key), reference.get(&key)),
+            }
+            assert_eq!(map.len(), reference.len());
+        }
+        for (key, value) in &reference {
+            assert_eq!(map.get(key), Some(value));
+        }
+    }
+
+    #[test]
+    fn clone_and_equality() {
+        let map: StackMap<i32, &str, 4> = [(1, "one"), (2, "two")].into();
+        let mut clone = map.clone();
+        assert_eq!(map, clone);
+        clone.insert(3, "three").unwrap();
+        assert_ne!(map, clone);
+        clone.remove(&3);
+        assert_eq!(map, clone);
+        assert_eq!(format!("{:?}", StackMap::<i32, i32, 2>::new()), "{}");
+    }
+
+    #[test]
+    fn drops_every_value_once() {
+        let drops = Cell::new(0);
+        {
+            let mut map = StackMap::<i32, DropCounter, 4>::new();
+            for key in 0..4 {
+                map.insert(key, DropCounter::new(0, &drops)).unwrap();
+            }
+            map.insert(0, DropCounter::new(0, &drops)).unwrap();
+            assert_eq!(drops.get(), 1);
+            map.remove(&1);
+            assert_eq!(drops.get(), 2);
+            let mut iter = map.into_iter();
+            iter.next();
+            assert_eq!(drops.get(), 3);
+        }
+        assert_eq!(drops.get(), 5);
+    }
+}
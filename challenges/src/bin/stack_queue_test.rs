@@ -1,8 +1,45 @@
+fn main() {
+    let mut queue = challenges::stack_queue::StackQueue::<i32, 4>::new();
+
+    for i in 0..6 {
+        match queue.push(i) {
+            Ok(()) => println!("Pushed {}", i),
+            Err(e) => println!("Failed to push {}: {}", i, e),
+        }
+    }
+
+    println!("Queue: {:?}, size: {}", queue, queue.len());
+
+    while let Some(item) = queue.pop() {
+        println!("Popped {}", item);
+    }
+}
+
+#[cfg(test)]
+#[path = "common/drop_counter.rs"]
+mod drop_counter;
+
 #[cfg(test)]
 mod tests {
-    use challenges::stack_queue::StackQueue;
+    use crate::drop_counter::DropCounter;
+    use challenges::stack_queue::{QueueFullError, StackQueue};
+    use std::cell::Cell;
     use std::iter::FromIterator;
 
+    /// Builds a queue whose storage wraps around the end of the array.
+    fn wrapped<'a>(drops: &'a Cell<usize>, values: &[i32]) -> StackQueue<DropCounter<'a>, 5> {
+        let mut queue = StackQueue::new();
+        for _ in 0..3 {
+            queue.push(DropCounter::new(0, drops)).unwrap();
+            queue.pop();
+        }
+        for &value in values {
+            queue.push(DropCounter::new(value, drops)).unwrap();
+        }
+        drops.set(0);
+        queue
+    }
+
     #[test]
     fn new_queue_is_empty() {
         let queue: StackQueue<i32, 5> = StackQueue::new();
@@ -35,7 +72,7 @@ mod tests {
         queue.push(1).unwrap();
         queue.push(2).unwrap();
         assert!(queue.is_full());
-        assert_eq!(queue.push(3), Err("Queue is full".to_string()));
+        assert_eq!(queue.push(3), Err(QueueFullError::new(3)));
     }
 
     #[test]
@@ -59,4 +96,376 @@ mod tests {
         queue.extend(vec![1, 2, 3]);
         assert_eq!(queue.len(), 3);
     }
+
+    static READY: StackQueue<u8, 3> = StackQueue::from_array([1, 2, 3]);
+
+    #[test]
+    fn const_constructed_queue() {
+        assert!(READY.is_full());
+        assert_eq!(READY.front(), Some(&1));
+        assert_eq!(READY.back(), Some(&3));
+    }
+
+    #[test]
+    fn from_array_pops_in_order() {
+        let mut queue = StackQueue::from_array([1, 2, 3]);
+        assert_eq!(queue.pop(), Some(1));
+        queue.push(4).unwrap();
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), Some(4));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn clear_drops_elements() {
+        let drops = Cell::new(0);
+        let mut queue = wrapped(&drops, &[1, 2, 3, 4]);
+        queue.clear();
+        assert_eq!(drops.get(), 4);
+        assert!(queue.is_empty());
+        queue.push(DropCounter::new(5, &drops)).ok().unwrap();
+        assert_eq!(queue.front().map(|e| e.value), Some(5));
+        drop(queue);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn truncate_front_keeps_newest() {
+        let drops = Cell::new(0);
+        let mut queue = wrapped(&drops, &[1, 2, 3, 4]);
+        queue.truncate_front(1);
+        assert_eq!(drops.get(), 3);
+        assert_eq!(queue.front().map(|e| e.value), Some(4));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn truncate_back_keeps_oldest() {
+        let drops = Cell::new(0);
+        let mut queue = wrapped(&drops, &[1, 2, 3, 4]);
+        queue.truncate_back(2);
+        assert_eq!(drops.get(), 2);
+        assert_eq!(queue.back().map(|e| e.value), Some(2));
+        queue.push(DropCounter::new(5, &drops)).ok().unwrap();
+        let values: Vec<i32> = queue.drain(..).map(|e| e.value).collect();
+        assert_eq!(values, vec![1, 2, 5]);
+    }
+
+    #[test]
+    fn drain_all_in_fifo_order() {
+        let drops = Cell::new(0);
+        let mut queue = wrapped(&drops, &[1, 2, 3, 4]);
+        let values: Vec<i32> = queue.drain(..).map(|e| e.value).collect();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+        assert_eq!(drops.get(), 4);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn abandoned_drain_drops_rest() {
+        let drops = Cell::new(0);
+        let mut queue = wrapped(&drops, &[1, 2, 3, 4, 5]);
+        {
+            let mut drain = queue.drain(..3);
+            assert_eq!(drain.next().map(|e| e.value), Some(1));
+        }
+        assert_eq!(drops.get(), 3);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.front().map(|e| e.value), Some(4));
+        drop(queue);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end overflow")]
+    fn drain_to_usize_max_panics() {
+        let mut queue = StackQueue::<i32, 4>::new();
+        queue.push(1).unwrap();
+        queue.drain(..=usize::MAX);
+    }
+
+    #[test]
+    fn drain_middle_closes_gap() {
+        let drops = Cell::new(0);
+        let mut queue = wrapped(&drops, &[1, 2, 3, 4, 5]);
+        let values: Vec<i32> = queue.drain(1..3).rev().map(|e| e.value).collect();
+        assert_eq!(values, vec![3, 2]);
+        queue.push(DropCounter::new(6, &drops)).ok().unwrap();
+        let rest: Vec<i32> = queue.drain(..).map(|e| e.value).collect();
+        assert_eq!(rest, vec![1, 4, 5, 6]);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn push_front_and_pop_back() {
+        let mut queue = StackQueue::<i32, 3>::new();
+        queue.push_front(2).unwrap();
+        queue.push_front(1).unwrap();
+        queue.push_back(3).unwrap();
+        assert!(queue.push_front(0).is_err());
+        assert_eq!(queue.pop_back(), Some(3));
+        assert_eq!(queue.pop_front(), Some(1));
+        assert_eq!(queue.pop_back(), Some(2));
+        assert_eq!(queue.pop_back(), None);
+    }
+
+    #[test]
+    fn logical_indexing_across_wrap() {
+        let mut queue = StackQueue::<i32, 4>::new();
+        queue.extend([0, 0, 1, 2]);
+        queue.pop();
+        queue.pop();
+        queue.push(3).unwrap();
+        queue.push(4).unwrap();
+        assert_eq!(queue.get(0), Some(&1));
+        assert_eq!(queue[3], 4);
+        assert_eq!(queue.get(4), None);
+        queue[1] = 20;
+        assert_eq!(queue.as_slices(), (&[1, 20][..], &[3, 4][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds_panics() {
+        let queue = StackQueue::<i32, 4>::from_iter([1]);
+        let _ = queue[1];
+    }
+
+    #[test]
+    fn as_slices_contiguous() {
+        let mut queue = StackQueue::<i32, 4>::from_iter([1, 2]);
+        assert_eq!(queue.as_slices(), (&[1, 2][..], &[][..]));
+        let (head, _) = queue.as_mut_slices();
+        head[0] = 10;
+        assert_eq!(queue.front(), Some(&10));
+    }
+
+    #[test]
+    fn rotate_partial_queue() {
+        let mut queue = StackQueue::<i32, 6>::from_iter([1, 2, 3, 4]);
+        queue.rotate_left(1);
+        assert_eq!(queue.drain(..).collect::<Vec<_>>(), vec![2, 3, 4, 1]);
+        queue.extend([1, 2, 3, 4]);
+        queue.rotate_right(1);
+        assert_eq!(queue.drain(..).collect::<Vec<_>>(), vec![4, 1, 2, 3]);
+    }
+
+    #[test]
+    fn rotate_full_queue() {
+        let mut queue = StackQueue::from_array([1, 2, 3, 4, 5]);
+        queue.rotate_left(3);
+        assert_eq!(queue.back(), Some(&3));
+        queue.push_front(0).unwrap_err();
+        queue.rotate_right(4);
+        assert_eq!(queue.drain(..).collect::<Vec<_>>(), vec![5, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn push_overwrite_evicts_oldest() {
+        let mut queue = StackQueue::<i32, 3>::new();
+        assert_eq!(queue.push_overwrite(1), None);
+        assert_eq!(queue.push_overwrite(2), None);
+        assert_eq!(queue.push_overwrite(3), None);
+        assert_eq!(queue.push_overwrite(4), Some(1));
+        assert_eq!(queue.push_overwrite(5), Some(2));
+        assert_eq!(queue.front(), Some(&3));
+        assert_eq!(queue.back(), Some(&5));
+        assert_eq!(queue.len(), 3);
+    }
+
+    #[test]
+    fn push_overwrite_drops_nothing_twice() {
+        let drops = Cell::new(0);
+        let mut queue = StackQueue::<DropCounter, 2>::new();
+        for value in 0..5 {
+            queue.push_overwrite(DropCounter::new(value, &drops));
+        }
+        assert_eq!(drops.get(), 3);
+        assert_eq!(queue.front().map(|e| e.value), Some(3));
+        drop(queue);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn make_contiguous_full_window() {
+        let mut queue = StackQueue::<i32, 4>::new();
+        for sample in 0..10 {
+            queue.push_overwrite(sample);
+        }
+        assert_eq!(queue.make_contiguous(), &[6, 7, 8, 9]);
+        queue.push_overwrite(10);
+        assert_eq!(queue.make_contiguous(), &[7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn make_contiguous_partial_wrapped() {
+        let drops = Cell::new(0);
+        let mut queue = wrapped(&drops, &[1, 2, 3, 4]);
+        let values: Vec<i32> = queue.make_contiguous().iter().map(|e| e.value).collect();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+        assert_eq!(queue.as_slices().1.len(), 0);
+        queue.push(DropCounter::new(5, &drops)).unwrap();
+        assert_eq!(queue.back().map(|e| e.value), Some(5));
+        drop(queue);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn full_error_returns_item() {
+        let mut queue = StackQueue::<String, 1>::new();
+        queue.push("kept".to_string()).unwrap();
+        let err = queue.push("rejected".to_string()).unwrap_err();
+        assert_eq!(err.to_string(), "Queue is full");
+        assert_eq!(err.item(), "rejected");
+        let err = queue.push_front("front".to_string()).unwrap_err();
+        assert_eq!(err.item(), "front");
+    }
+
+    #[test]
+    fn full_error_is_std_error() {
+        fn boxed() -> Result<(), Box<dyn std::error::Error>> {
+            let mut queue = StackQueue::<i32, 0>::new();
+            queue.push(1)?;
+            Ok(())
+        }
+        assert!(boxed().is_err());
+    }
+
+    #[test]
+    fn extend_counted_reports_dropped() {
+        let mut queue = StackQueue::<i32, 3>::new();
+        assert_eq!(queue.extend_counted([1, 2]), 0);
+        assert_eq!(queue.extend_counted([3, 4, 5]), 2);
+        assert_eq!(queue.drain(..).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn from_iter_counted_reports_dropped() {
+        let (queue, dropped) = StackQueue::<i32, 2>::from_iter_counted(1..=5);
+        assert_eq!(dropped, 3);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.back(), Some(&2));
+    }
+
+    #[test]
+    fn extend_drops_overflowing_items() {
+        let drops = Cell::new(0);
+        let mut queue = StackQueue::<DropCounter, 2>::new();
+        queue.extend((0..4).map(|value| DropCounter::new(value, &drops)));
+        assert_eq!(drops.get(), 2);
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_in_fifo_order() {
+        let mut queue = StackQueue::<i32, 3>::new();
+        queue.extend([0, 1, 2]);
+        queue.pop();
+        queue.push(3).unwrap();
+        let json = serde_json::to_string(&queue).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        let mut decoded: StackQueue<i32, 3> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.drain(..).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_overflow() {
+        let result = serde_json::from_str::<StackQueue<i32, 2>>("[1, 2, 3]");
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("at most 2 elements"), "{}", err);
+    }
+
+    /// Builds `[1, 2, 3, 4]` with storage that starts at a different offset each time.
+    fn rotated(offset: usize) -> StackQueue<i32, 5> {
+        let mut queue = StackQueue::new();
+        for _ in 0..offset {
+            queue.push(0).unwrap();
+            queue.pop();
+        }
+        queue.extend([1, 2, 3, 4]);
+        queue
+    }
+
+    #[test]
+    fn iter_crosses_wrap_boundary() {
+        let queue = rotated(3);
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            queue.iter().rev().copied().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(queue.iter().len(), 4);
+        assert_eq!((&queue).into_iter().sum::<i32>(), 10);
+    }
+
+    #[test]
+    fn iter_mut_modifies_in_order() {
+        let mut queue = rotated(4);
+        for (i, item) in queue.iter_mut().enumerate() {
+            *item *= i as i32;
+        }
+        for item in &mut queue {
+            *item += 1;
+        }
+        assert_eq!(queue.iter().copied().collect::<Vec<_>>(), vec![1, 3, 7, 13]);
+    }
+
+    #[test]
+    fn into_iter_is_double_ended() {
+        let mut iter = rotated(2).into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn into_iter_drops_unconsumed() {
+        let drops = Cell::new(0);
+        let queue = wrapped(&drops, &[1, 2, 3, 4]);
+        let mut iter = queue.into_iter();
+        assert_eq!(iter.next().map(|e| e.value), Some(1));
+        assert_eq!(drops.get(), 1);
+        drop(iter);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn equality_ignores_storage_offset() {
+        assert_eq!(rotated(0), rotated(3));
+        assert_ne!(rotated(0), StackQueue::from_iter([1, 2, 3]));
+        assert_eq!(rotated(1).clone(), rotated(4));
+    }
+
+    #[test]
+    fn hash_ignores_storage_offset() {
+        use std::collections::HashSet;
+        let mut set = HashSet::new();
+        set.insert(rotated(0));
+        assert!(set.contains(&rotated(2)));
+        assert!(!set.contains(&StackQueue::from_iter([1, 2, 3])));
+    }
+
+    #[test]
+    fn ordering_is_lexicographic() {
+        let shorter = StackQueue::<i32, 5>::from_iter([1, 2, 3]);
+        let larger = StackQueue::<i32, 5>::from_iter([1, 3]);
+        assert!(shorter < rotated(1));
+        assert!(rotated(1) < larger);
+        assert_eq!(rotated(0).cmp(&rotated(3)), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        let original = StackQueue::<String, 3>::from_iter(["a".to_string(), "b".to_string()]);
+        let mut copy = original.clone();
+        copy.push("c".to_string()).unwrap();
+        assert_eq!(original.len(), 2);
+        assert_eq!(copy.len(), 3);
+        assert_eq!(copy.back().map(String::as_str), Some("c"));
+        assert_eq!(original.back().map(String::as_str), Some("b"));
+    }
 }
@@ -0,0 +1,66 @@
+use challenges::stack_set::StackSet;
+
+fn main() {
+    let mut seen = StackSet::<u32, 8>::new();
+    for id in [3, 1, 3, 7, 1] {
+        if !seen.insert(id).unwrap() {
+            println!("Duplicate id {}", id);
+        }
+    }
+    println!("{:?}", seen);
+}
+
+#[cfg(test)]
+mod tests {
+    use challenges::stack_set::StackSet;
+
+    #[test]
+    fn insert_contains_remove() {
+        let mut set = StackSet::<String, 4>::new();
+        assert!(set.insert("a".to_string()).unwrap());
+        assert!(!set.insert("a".to_string()).unwrap());
+        assert!(set.insert("b".to_string()).unwrap());
+        assert!(set.contains("a"));
+        assert_eq!(set.get("b").map(String::as_str), Some("b"));
+        assert!(set.remove("a"));
+        assert!(!set.remove("a"));
+        assert_eq!(set.take("b"), Some("b".to_string()));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn insert_returns_element_when_full() {
+        let mut set = StackSet::<i32, 2>::new();
+        set.insert(1).unwrap();
+        set.insert(2).unwrap();
+        assert!(set.is_full());
+        assert_eq!(set.insert(3).unwrap_err().element(), 3);
+        assert!(!set.insert(2).unwrap());
+    }
+
+    #[test]
+    fn retain_and_iteration() {
+        let mut set: StackSet<i32, 16> = (0..10).collect();
+        set.retain(|value| value % 3 == 0);
+        let mut values: Vec<_> = set.iter().copied().collect();
+        values.sort();
+        assert_eq!(values, vec![0, 3, 6, 9]);
+
+        let mut owned: Vec<_> = set.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, vec![0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn subset_disjoint_and_equality() {
+        let small: StackSet<i32, 4> = [1, 2].into();
+        let large: StackSet<i32, 8> = [1, 2, 3].into();
+        let other: StackSet<i32, 4> = [4, 5].into();
+        assert!(small.is_subset(&large));
+        assert!(!large.is_subset(&small));
+        assert!(small.is_disjoint(&other));
+        assert!(!small.is_disjoint(&large));
+        assert_eq!(small, [2, 1].into());
+        assert_eq!(small.clone(), small);
+    }
+}
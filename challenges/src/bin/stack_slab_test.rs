@@ -0,0 +1,147 @@
+use challenges::stack_slab::StackSlab;
+
+fn main() {
+    let mut points = StackSlab::<(f32, f32, f32), 4>::new();
+    let first = points.insert((1.0, 2.0, 3.0)).unwrap();
+    let second = points.insert((4.0, 5.0, 6.0)).unwrap();
+    println!("{:?}", points);
+
+    points.remove(first);
+    let reused = points.insert((7.0, 8.0, 9.0)).unwrap();
+    println!(
+        "Reused slot {}, stale handle valid: {}, other handle valid: {}",
+        reused.index(),
+        points.contains(first),
+        points.contains(second)
+    );
+}
+
+#[cfg(test)]
+#[path = "common/drop_counter.rs"]
+mod drop_counter;
+
+#[cfg(test)]
+mod tests {
+    use crate::drop_counter::DropCounter;
+    use challenges::stack_slab::StackSlab;
+    use std::cell::Cell;
+
+    #[test]
+    fn insert_get_remove() {
+        let mut slab = StackSlab::<&str, 4>::new();
+        let a = slab.insert("a").unwrap();
+        let b = slab.insert("b").unwrap();
+        assert_eq!(slab.len(), 2);
+        assert_eq!(slab.get(a), Some(&"a"));
+        assert_eq!(slab[b], "b");
+        *slab.get_mut(b).unwrap() = "bb";
+        assert_eq!(slab.remove(b), Some("bb"));
+        assert_eq!(slab.remove(b), None);
+        assert!(!slab.contains(b));
+        assert!(slab.contains(a));
+        assert_eq!(slab.len(), 1);
+    }
+
+    #[test]
+    fn insert_returns_value_when_full() {
+        let mut slab = StackSlab::<i32, 2>::new();
+        slab.insert(1).unwrap();
+        let second = slab.insert(2).unwrap();
+        assert!(slab.is_full());
+        assert_eq!(slab.insert(3).unwrap_err().element(), 3);
+        slab.remove(second);
+        assert!(slab.insert(3).is_ok());
+    }
+
+    #[test]
+    fn stale_handles_are_detected_after_reuse() {
+        let mut slab = StackSlab::<i32, 2>::new();
+        let old = slab.insert(1).unwrap();
+        slab.remove(old);
+        let new = slab.insert(2).unwrap();
+        assert_eq!(new.index(), old.index());
+        assert_ne!(new.generation(), old.generation());
+        assert_eq!(slab.get(old), None);
+        assert_eq!(slab.get_mut(old), None);
+        assert_eq!(slab.remove(old), None);
+        assert_eq!(slab.get(new), Some(&2));
+    }
+
+    #[test]
+    #[should_panic(expected = "stale handle")]
+    fn index_panics_on_stale_handle() {
+        let mut slab = StackSlab::<i32, 1>::new();
+        let handle = slab.insert(1).unwrap();
+        slab.clear();
+        let _ = slab[handle];
+    }
+
+    #[test]
+    fn freed_slots_are_reused_last_in_first_out() {
+        let mut slab = StackSlab::<i32, 4>::new();
+        let handles: Vec<_> = (0..4).map(|i| slab.insert(i).unwrap()).collect();
+        slab.remove(handles[1]);
+        slab.remove(handles[3]);
+        assert_eq!(slab.insert(10).unwrap().index(), 3);
+        assert_eq!(slab.insert(11).unwrap().index(), 1);
+        assert!(slab.is_full());
+    }
+
+    #[test]
+    fn iteration_skips_free_slots() {
+        let mut slab = StackSlab::<i32, 8>::new();
+        let handles: Vec<_> = (0..5).map(|i| slab.insert(i).unwrap()).collect();
+        slab.remove(handles[0]);
+        slab.remove(handles[3]);
+        let iter = slab.iter();
+        assert_eq!(iter.len(), 3);
+        let live: Vec<_> = iter.map(|(handle, value)| (handle, *value)).collect();
+        assert_eq!(
+            live,
+            vec![(handles[1], 1), (handles[2], 2), (handles[4], 4)]
+        );
+
+        for (_, value) in &mut slab {
+            *value *= 10;
+        }
+        assert_eq!(slab[handles[4]], 40);
+    }
+
+    #[test]
+    fn retain_removes_rejected_entries() {
+        let mut slab = StackSlab::<i32, 8>::new();
+        let handles: Vec<_> = (0..6).map(|i| slab.insert(i).unwrap()).collect();
+        slab.retain(|_, value| *value % 2 == 0);
+        assert_eq!(slab.len(), 3);
+        assert!(slab.contains(handles[2]));
+        assert!(!slab.contains(handles[3]));
+    }
+
+    #[test]
+    fn clone_keeps_handles_valid() {
+        let mut slab = StackSlab::<String, 4>::new();
+        let a = slab.insert("a".to_string()).unwrap();
+        let b = slab.insert("b".to_string()).unwrap();
+        slab.remove(a);
+        let mut clone = slab.clone();
+        assert_eq!(clone[b], "b");
+        assert!(!clone.contains(a));
+        assert_eq!(clone.insert("c".to_string()).unwrap().index(), a.index());
+    }
+
+    #[test]
+    fn drops_every_value_once() {
+        let drops = Cell::new(0);
+        {
+            let mut slab = StackSlab::<DropCounter, 4>::new();
+            let handles: Vec<_> = (0..3)
+                .map(|value| slab.insert(DropCounter::new(value, &drops)).ok().unwrap())
+                .collect();
+            assert_eq!(slab.remove(handles[1]).map(|e| e.value), Some(1));
+            assert_eq!(drops.get(), 1);
+            slab.retain(|_, entry| entry.value != 0);
+            assert_eq!(drops.get(), 2);
+        }
+        assert_eq!(drops.get(), 3);
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::Write;
+
+use challenges::stack_string::StackString;
+
+fn main() {
+    let mut sensor_name = StackString::<16>::new();
+    sensor_name.push_str("lidar");
+    write!(sensor_name, "-front-{}", 2).unwrap();
+    println!(
+        "Sensor: {}, {} of {} bytes",
+        sensor_name,
+        sensor_name.len(),
+        sensor_name.capacity()
+    );
+
+    match sensor_name.try_push_str("-with-a-long-suffix") {
+        Ok(()) => println!("Appended suffix"),
+        Err(e) => println!("Rejected {:?}: {}", e.element(), e),
+    }
+
+    let frame_id = StackString::<8>::from_str_truncating("frame-ünïcode");
+    println!("Truncated frame id: {:?}", frame_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use challenges::stack_string::StackString;
+    use std::collections::HashMap;
+    use std::fmt::Write;
+    use std::str::FromStr;
+
+    #[test]
+    fn push_and_deref() {
+        let mut string = StackString::<8>::new();
+        string.push_str("ab");
+        string.push('c');
+        assert_eq!(string, "abc");
+        assert_eq!(string.to_uppercase(), "ABC");
+        assert!(string.starts_with("ab"));
+        assert_eq!(string.remaining_capacity(), 5);
+    }
+
+    #[test]
+    fn try_push_str_is_all_or_nothing() {
+        let mut string = StackString::<4>::new();
+        string.try_push_str("abc").unwrap();
+        let err = string.try_push_str("de").unwrap_err();
+        assert_eq!(err.element(), "de");
+        assert_eq!(string, "abc");
+        assert_eq!(string.try_push('é').unwrap_err().element(), 'é');
+        string.try_push('d').unwrap();
+        assert_eq!(string, "abcd");
+    }
+
+    #[test]
+    fn fmt_write_reports_overflow() {
+        let mut string = StackString::<8>::new();
+        write!(string, "id={}", 42).unwrap();
+        assert_eq!(string, "id=42");
+        assert!(write!(string, "{}", 1234).is_err());
+    }
+
+    #[test]
+    fn from_str_checks_capacity() {
+        let string = StackString::<5>::from_str("hello").unwrap();
+        assert_eq!(string.to_string(), "hello");
+        assert!(StackString::<4>::from_str("hello").is_err());
+        assert!(StackString::<4>::try_from("hello").is_err());
+    }
+
+    #[test]
+    fn truncating_respects_char_boundaries() {
+        // 'ü' and 'ï' are two bytes each
+        let string = StackString::<3>::from_str_truncating("aüb");
+        assert_eq!(string, "aü");
+        let string = StackString::<2>::from_str_truncating("aüb");
+        assert_eq!(string, "a");
+
+        let mut string = StackString::<8>::from_str("ïïï").unwrap();
+        string.truncate_to_char_boundary(3);
+        assert_eq!(string, "ï");
+        assert_eq!(string.push_str_truncating("ab"), 2);
+        assert_eq!(string.push_str_truncating("üüü"), 4);
+        assert_eq!(string, "ïabüü");
+    }
+
+    #[test]
+    #[should_panic]
+    fn truncate_inside_char_panics() {
+        let mut string = StackString::<8>::from_str("ü").unwrap();
+        string.truncate(1);
+    }
+
+    #[test]
+    fn pop_returns_whole_chars() {
+        let mut string = StackString::<8>::from_str("aü").unwrap();
+        assert_eq!(string.pop(), Some('ü'));
+        assert_eq!(string.pop(), Some('a'));
+        assert_eq!(string.pop(), None);
+    }
+
+    #[test]
+    fn hash_and_ord_match_str() {
+        let mut map = HashMap::new();
+        map.insert(StackString::<8>::from_str("front").unwrap(), 1);
+        assert_eq!(map.get("front"), Some(&1));
+
+        let a = StackString::<8>::from_str("abc").unwrap();
+        let b = StackString::<8>::from_str("abd").unwrap();
+        assert!(a < b);
+        assert_eq!(format!("{:?}", a), "\"abc\"");
+        assert_eq!(String::from(b), "abd");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let string = StackString::<8>::from_str("frame").unwrap();
+        let json = serde_json::to_string(&string).unwrap();
+        assert_eq!(json, "\"frame\"");
+        let decoded: StackString<8> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, string);
+        assert!(serde_json::from_str::<StackString<2>>(&json).is_err());
+    }
+}
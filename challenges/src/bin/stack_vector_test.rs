@@ -43,4 +43,452 @@ fn main() {
     }));
 
     assert!(result.is_err(), "Pushing beyond capacity should panic");
+
+    stack_vector.clear();
+    for i in 0..15 {
+        if let Err(e) = stack_vector.try_push(i) {
+            println!("Rejected {:?}: {}", e.element(), e);
+            break;
+        }
+    }
+    println!(
+        "After try_push: {:?}, remaining capacity: {}",
+        stack_vector,
+        stack_vector.remaining_capacity()
+    );
+}
+
+#[cfg(test)]
+#[path = "common/drop_counter.rs"]
+mod drop_counter;
+
+#[cfg(test)]
+mod tests {
+    use crate::drop_counter::DropCounter;
+    use challenges::stack_vec;
+    use challenges::stack_vector::{CapacityError, StackVector};
+    use std::cell::Cell;
+    use std::ops::Bound;
+
+    fn counted<'a>(drops: &'a Cell<usize>, values: &[i32]) -> StackVector<DropCounter<'a>, 8> {
+        let mut vector = StackVector::new();
+        for &value in values {
+            vector.push(DropCounter::new(value, drops));
+        }
+        vector
+    }
+
+    #[test]
+    fn try_push_within_capacity() {
+        let mut vector = StackVector::<i32, 2>::new();
+        assert_eq!(vector.try_push(1), Ok(()));
+        assert_eq!(vector.try_push(2), Ok(()));
+        assert!(vector.is_full());
+        assert_eq!(vector.remaining_capacity(), 0);
+    }
+
+    #[test]
+    fn try_push_returns_rejected_value() {
+        let mut vector = StackVector::<String, 1>::new();
+        vector.try_push("a".to_string()).unwrap();
+        let err = vector.try_push("b".to_string()).unwrap_err();
+        assert_eq!(err.element(), "b");
+        assert_eq!(vector.len(), 1);
+    }
+
+    #[test]
+    fn try_insert_shifts_elements() {
+        let mut vector = StackVector::<i32, 4>::new();
+        vector.push(1);
+        vector.push(3);
+        assert_eq!(vector.try_insert(1, 2), Ok(()));
+        assert_eq!(vector.try_insert(3, 4), Ok(()));
+        assert_eq!(vector.try_insert(0, 0), Err(CapacityError::new(0)));
+        assert!(vector.iter().copied().eq([1, 2, 3, 4]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn try_insert_out_of_bounds_panics() {
+        let mut vector = StackVector::<i32, 4>::new();
+        let _ = vector.try_insert(1, 0);
+    }
+
+    #[test]
+    fn try_extend_from_slice_is_all_or_nothing() {
+        let mut vector = StackVector::<i32, 4>::new();
+        assert_eq!(vector.try_extend_from_slice(&[1, 2]), Ok(()));
+        assert!(vector.try_extend_from_slice(&[3, 4, 5]).is_err());
+        assert_eq!(vector.len(), 2);
+        assert_eq!(vector.try_extend_from_slice(&[3, 4]), Ok(()));
+        assert!(vector.iter().copied().eq([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn capacity_error_display() {
+        let err = CapacityError::new(5);
+        assert_eq!(err.to_string(), "capacity exceeded");
+        assert_eq!(err.simplify(), CapacityError::new(()));
+    }
+
+    #[test]
+    fn deref_to_slice() {
+        let mut vector = StackVector::<i32, 8>::new();
+        vector.try_extend_from_slice(&[5, 3, 1, 4]).unwrap();
+        vector.sort();
+        assert_eq!(&vector[..], &[1, 3, 4, 5]);
+        assert_eq!(vector.binary_search(&4), Ok(2));
+        assert_eq!(vector.chunks(2).count(), 2);
+        assert_eq!(vector.as_ref(), &[1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn insert_and_remove() {
+        let mut vector = StackVector::<i32, 4>::new();
+        vector.insert(0, 2);
+        vector.insert(0, 1);
+        vector.insert(2, 3);
+        assert_eq!(&vector[..], &[1, 2, 3]);
+        assert_eq!(vector.remove(1), 2);
+        assert_eq!(&vector[..], &[1, 3]);
+    }
+
+    #[test]
+    fn swap_remove_takes_last() {
+        let mut vector = StackVector::<i32, 4>::new();
+        vector.try_extend_from_slice(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(vector.swap_remove(0), 1);
+        assert_eq!(&vector[..], &[4, 2, 3]);
+    }
+
+    #[test]
+    fn truncate_drops_tail() {
+        let drops = Cell::new(0);
+        let mut vector = counted(&drops, &[1, 2, 3, 4]);
+        vector.truncate(1);
+        assert_eq!(drops.get(), 3);
+        assert_eq!(vector.len(), 1);
+        drop(vector);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn retain_drops_rejected() {
+        let drops = Cell::new(0);
+        let mut vector = counted(&drops, &[1, 2, 3, 4, 5]);
+        vector.retain(|e| e.value % 2 == 1);
+        assert_eq!(drops.get(), 2);
+        assert!(vector.iter().map(|e| e.value).eq([1, 3, 5]));
+    }
+
+    #[test]
+    fn retain_mut_modifies() {
+        let mut vector = StackVector::<i32, 4>::new();
+        vector.try_extend_from_slice(&[1, 2, 3, 4]).unwrap();
+        vector.retain_mut(|x| {
+            *x *= 10;
+            *x > 15
+        });
+        assert_eq!(&vector[..], &[20, 30, 40]);
+    }
+
+    #[test]
+    fn retain_panic_keeps_vector_consistent() {
+        let drops = Cell::new(0);
+        let mut vector = counted(&drops, &[1, 2, 3, 4]);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vector.retain(|e| {
+                assert!(e.value != 3);
+                e.value != 1
+            });
+        }));
+        assert!(result.is_err());
+        assert!(vector.iter().map(|e| e.value).eq([2, 3, 4]));
+        drop(vector);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn dedup_by_key_drops_duplicates() {
+        let drops = Cell::new(0);
+        let mut vector = counted(&drops, &[1, 1, 2, 3, 3, 3, 1]);
+        vector.dedup_by_key(|e| e.value);
+        assert_eq!(drops.get(), 3);
+        assert!(vector.iter().map(|e| e.value).eq([1, 2, 3, 1]));
+    }
+
+    #[test]
+    fn split_off_moves_tail() {
+        let drops = Cell::new(0);
+        let mut vector = counted(&drops, &[1, 2, 3, 4]);
+        let tail = vector.split_off(1);
+        assert!(vector.iter().map(|e| e.value).eq([1]));
+        assert!(tail.iter().map(|e| e.value).eq([2, 3, 4]));
+        drop(tail);
+        drop(vector);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    fn resize_with_grows_and_shrinks() {
+        let mut vector = StackVector::<i32, 4>::new();
+        let mut next = 0;
+        vector.resize_with(3, || {
+            next += 1;
+            next
+        });
+        assert_eq!(&vector[..], &[1, 2, 3]);
+        vector.resize_with(1, || 0);
+        assert_eq!(&vector[..], &[1]);
+    }
+
+    #[test]
+    fn drain_middle_range() {
+        let mut vector = StackVector::<i32, 8>::new();
+        vector.try_extend_from_slice(&[1, 2, 3, 4, 5]).unwrap();
+        let drained: Vec<i32> = vector.drain(1..4).collect();
+        assert_eq!(drained, vec![2, 3, 4]);
+        assert_eq!(&vector[..], &[1, 5]);
+    }
+
+    #[test]
+    fn drain_double_ended() {
+        let mut vector = StackVector::<i32, 8>::new();
+        vector.try_extend_from_slice(&[1, 2, 3, 4]).unwrap();
+        let mut drain = vector.drain(..);
+        assert_eq!(drain.len(), 4);
+        assert_eq!(drain.next_back(), Some(4));
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.as_slice(), &[2, 3]);
+        drop(drain);
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "range end overflow")]
+    fn drain_to_usize_max_panics() {
+        let mut vector = StackVector::<i32, 4>::new();
+        vector.push(1);
+        vector.drain(..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start overflow")]
+    fn drain_from_after_usize_max_panics() {
+        let mut vector = StackVector::<i32, 4>::new();
+        vector.drain((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    fn abandoned_drain_drops_remaining() {
+        let drops = Cell::new(0);
+        let mut vector = counted(&drops, &[1, 2, 3, 4, 5]);
+        {
+            let mut drain = vector.drain(1..=3);
+            let first = drain.next().unwrap();
+            assert_eq!(first.value, 2);
+        }
+        assert_eq!(drops.get(), 3);
+        assert!(vector.iter().map(|e| e.value).eq([1, 5]));
+        drop(vector);
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn into_iter_yields_in_order() {
+        let mut vector = StackVector::<String, 4>::new();
+        vector.push("a".to_string());
+        vector.push("b".to_string());
+        vector.push("c".to_string());
+        let collected: Vec<String> = vector.into_iter().collect();
+        assert_eq!(collected, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn into_iter_fully_consumed_drops_each_once() {
+        let drops = Cell::new(0);
+        let vector = counted(&drops, &[1, 2, 3]);
+        for element in vector {
+            assert_eq!(drops.get(), element.value as usize - 1);
+        }
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn into_iter_abandoned_drops_remaining() {
+        let drops = Cell::new(0);
+        let vector = counted(&drops, &[1, 2, 3, 4, 5]);
+        let mut iter = vector.into_iter();
+        let first = iter.next().unwrap();
+        let last = iter.next_back().unwrap();
+        assert_eq!((first.value, last.value), (1, 5));
+        assert_eq!(drops.get(), 0);
+        drop(iter);
+        assert_eq!(drops.get(), 3);
+        drop((first, last));
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn into_iter_unused_drops_all() {
+        let drops = Cell::new(0);
+        let vector = counted(&drops, &[1, 2, 3]);
+        drop(vector.into_iter());
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn into_iter_double_ended_and_exact_size() {
+        let mut vector = StackVector::<i32, 8>::new();
+        vector.try_extend_from_slice(&[1, 2, 3, 4, 5]).unwrap();
+        let mut iter = vector.into_iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.as_slice(), &[2, 3, 4]);
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.by_ref().rev().collect::<Vec<_>>(), vec![4, 3, 2]);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn into_iter_clone_is_independent() {
+        let mut vector = StackVector::<String, 4>::new();
+        vector.push("x".to_string());
+        vector.push("y".to_string());
+        let mut iter = vector.into_iter();
+        iter.next();
+        let cloned = iter.clone();
+        assert_eq!(iter.as_slice(), cloned.as_slice());
+        assert_eq!(cloned.collect::<Vec<_>>(), vec!["y"]);
+    }
+
+    #[test]
+    fn iterate_by_reference() {
+        let mut vector = StackVector::<i32, 4>::new();
+        vector.try_extend_from_slice(&[1, 2, 3]).unwrap();
+        for value in &mut vector {
+            *value += 1;
+        }
+        let sum: i32 = (&vector).into_iter().sum();
+        assert_eq!(sum, 9);
+    }
+
+    static LOOKUP: StackVector<u8, 4> = stack_vec![1, 2, 4, 8];
+    const EMPTY: StackVector<u8, 4> = StackVector::new();
+    const LOOKUP_LEN: usize = LOOKUP.len();
+
+    #[test]
+    fn const_constructed_vectors() {
+        assert_eq!(&LOOKUP[..], &[1, 2, 4, 8]);
+        assert_eq!(LOOKUP_LEN, 4);
+        assert!(LOOKUP.is_full());
+        assert!(EMPTY.is_empty());
+    }
+
+    #[test]
+    fn from_array_takes_ownership() {
+        let drops = Cell::new(0);
+        let vector =
+            StackVector::from_array([DropCounter::new(1, &drops), DropCounter::new(2, &drops)]);
+        assert_eq!(vector.len(), 2);
+        assert_eq!(vector[1].value, 2);
+        assert_eq!(drops.get(), 0);
+        drop(vector);
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    fn stack_vec_macro_infers_capacity() {
+        let vector = stack_vec!["a".to_string(), "b".to_string()];
+        assert_eq!(vector.capacity(), 2);
+        let empty: StackVector<i32, 3> = stack_vec![];
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut vector = StackVector::<String, 4>::new();
+        vector.push("a".to_string());
+        vector.push("b".to_string());
+        let json = serde_json::to_string(&vector).unwrap();
+        assert_eq!(json, r#"["a","b"]"#);
+        let decoded: StackVector<String, 4> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, vector);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_overflow() {
+        let result = serde_json::from_str::<StackVector<i32, 2>>("[1, 2, 3]");
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("at most 2 elements"), "{}", err);
+    }
+
+    #[test]
+    fn usable_as_hash_map_key() {
+        use std::collections::HashMap;
+        let mut map = HashMap::new();
+        map.insert(StackVector::<u8, 4>::from([1, 2, 3, 4]), "frame");
+        let key: &[u8] = &[1, 2, 3, 4];
+        assert_eq!(map.get(key), Some(&"frame"));
+    }
+
+    #[test]
+    fn ordering_is_lexicographic() {
+        let a: StackVector<i32, 4> = [1, 2].into_iter().collect();
+        let b: StackVector<i32, 4> = [1, 2, 0].into_iter().collect();
+        let c: StackVector<i32, 4> = [1, 3].into_iter().collect();
+        assert!(a < b && b < c);
+        assert_eq!(a.cmp(&a.clone()), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn collect_and_extend() {
+        let mut vector: StackVector<i32, 6> = (1..=3).collect();
+        vector.extend(4..=5);
+        vector.extend(&[6]);
+        assert_eq!(&vector[..], &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn collect_beyond_capacity_panics() {
+        let _: StackVector<i32, 2> = (0..3).collect();
+    }
+
+    #[test]
+    fn try_from_slice_and_vec() {
+        let vector = StackVector::<i32, 3>::try_from(&[1, 2][..]).unwrap();
+        assert_eq!(&vector[..], &[1, 2]);
+        assert!(StackVector::<i32, 1>::try_from(&[1, 2][..]).is_err());
+
+        let vector = StackVector::<String, 2>::try_from(vec!["a".to_string()]).unwrap();
+        assert_eq!(vector[0], "a");
+        let err = StackVector::<i32, 1>::try_from(vec![1, 2]).unwrap_err();
+        assert_eq!(err.element(), vec![1, 2]);
+    }
+
+    #[test]
+    fn convert_into_vec() {
+        let vector = StackVector::from(["x".to_string(), "y".to_string()]);
+        let vec: Vec<String> = vector.into();
+        assert_eq!(vec, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn io_write_as_byte_sink() {
+        use std::io::Write;
+        let mut buffer = StackVector::<u8, 16>::new();
+        write!(buffer, "frame-{:04}", 42).unwrap();
+        assert_eq!(&buffer[..], b"frame-0042");
+
+        let mut small = StackVector::<u8, 4>::new();
+        assert_eq!(small.write(b"abcdef").unwrap(), 4);
+        assert_eq!(small.write(b"g").unwrap(), 0);
+        assert!(small.write_all(b"h").is_err());
+        assert_eq!(&small[..], b"abcd");
+    }
 }
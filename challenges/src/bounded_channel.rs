@@ -0,0 +1,403 @@
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use crate::stack_queue::StackQueue;
+
+/// Returned by `send` when the channel has been closed, carrying the item back.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    Full(T),
+    Closed(T),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SendTimeoutError<T> {
+    Timeout(T),
+    Closed(T),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    Empty,
+    Closed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    Timeout,
+    Closed,
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Channel is closed")
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "Full(..)"),
+            TrySendError::Closed(_) => write!(f, "Closed(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "Channel is full"),
+            TrySendError::Closed(_) => write!(f, "Channel is closed"),
+        }
+    }
+}
+
+impl<T> Error for TrySendError<T> {}
+
+impl<T> fmt::Debug for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => write!(f, "Timeout(..)"),
+            SendTimeoutError::Closed(_) => write!(f, "Closed(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => write!(f, "Timed out waiting for space"),
+            SendTimeoutError::Closed(_) => write!(f, "Channel is closed"),
+        }
+    }
+}
+
+impl<T> Error for SendTimeoutError<T> {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "Channel is empty"),
+            TryRecvError::Closed => write!(f, "Channel is closed"),
+        }
+    }
+}
+
+impl Error for TryRecvError {}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => write!(f, "Timed out waiting for data"),
+            RecvTimeoutError::Closed => write!(f, "Channel is closed"),
+        }
+    }
+}
+
+impl Error for RecvTimeoutError {}
+
+struct State<T, const N: usize> {
+    queue: StackQueue<T, N>,
+    closed: bool,
+    send_wakers: Vec<Waker>,
+    recv_wakers: Vec<Waker>,
+}
+
+/// Bounded multi-producer/multi-consumer channel over a `StackQueue`.
+///
+/// Senders wait for space and receivers wait for data, either blocking the
+/// thread on a `Condvar` or suspending a future until a `Waker` fires.
+/// Share it between threads with `Arc` or scoped threads. `N` must be at
+/// least 1, a zero-capacity channel is rejected at compile time.
+pub struct BoundedChannel<T, const N: usize> {
+    state: Mutex<State<T, N>>,
+    not_full: Condvar,
+    not_empty: Condvar,
+}
+
+impl<T, const N: usize> BoundedChannel<T, N> {
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "a channel needs a capacity of at least 1") };
+        Self {
+            state: Mutex::new(State {
+                queue: StackQueue::new(),
+                closed: false,
+                send_wakers: Vec::new(),
+                recv_wakers: Vec::new(),
+            }),
+            not_full: Condvar::new(),
+            not_empty: Condvar::new(),
+        }
+    }
+
+    pub fn try_send(&self, item: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(TrySendError::Closed(item));
+        }
+        match state.queue.push(item) {
+            Ok(()) => {
+                self.notify_receivers(state);
+                Ok(())
+            }
+            Err(e) => Err(TrySendError::Full(e.item())),
+        }
+    }
+
+    /// Blocks until there is space for `item` or the channel is closed.
+    pub fn send(&self, item: T) -> Result<(), SendError<T>> {
+        let mut state = self.lock();
+        while !state.closed && state.queue.is_full() {
+            state = self.not_full.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        self.push_locked(state, item).map_err(SendError)
+    }
+
+    /// Like `send` but gives up after `timeout`, a timeout too large for an
+    /// `Instant` waits forever.
+    pub fn send_timeout(&self, item: T, timeout: Duration) -> Result<(), SendTimeoutError<T>> {
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            return self
+                .send(item)
+                .map_err(|SendError(item)| SendTimeoutError::Closed(item));
+        };
+        let mut state = self.lock();
+        while !state.closed && state.queue.is_full() {
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(SendTimeoutError::Timeout(item));
+            }
+            state = self
+                .not_full
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        self.push_locked(state, item)
+            .map_err(SendTimeoutError::Closed)
+    }
+
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.lock();
+        match state.queue.pop() {
+            Some(item) => {
+                self.notify_senders(state);
+                Ok(item)
+            }
+            None if state.closed => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Blocks until an item arrives, returning `None` once the channel is closed and drained.
+    pub fn recv(&self) -> Option<T> {
+        let mut state = self.lock();
+        loop {
+            if let Some(item) = state.queue.pop() {
+                self.notify_senders(state);
+                return Some(item);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self
+                .not_empty
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Like `recv` but gives up after `timeout`, a timeout too large for an
+    /// `Instant` waits forever.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            return self.recv().ok_or(RecvTimeoutError::Closed);
+        };
+        let mut state = self.lock();
+        loop {
+            if let Some(item) = state.queue.pop() {
+                self.notify_senders(state);
+                return Ok(item);
+            }
+            if state.closed {
+                return Err(RecvTimeoutError::Closed);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(RecvTimeoutError::Timeout);
+            }
+            state = self
+                .not_empty
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    /// Returns a future that resolves once `item` has been queued or the channel is closed.
+    pub fn send_async(&self, item: T) -> SendFuture<'_, T, N> {
+        SendFuture {
+            channel: self,
+            item: Some(item),
+        }
+    }
+
+    /// Returns a future that resolves to the next item, or `None` once the channel is closed and drained.
+    pub fn recv_async(&self) -> RecvFuture<'_, T, N> {
+        RecvFuture { channel: self }
+    }
+
+    /// Closes the channel, waking every waiter. Items already queued can still be received.
+    pub fn close(&self) {
+        let mut state = self.lock();
+        state.closed = true;
+        let mut wakers = mem::take(&mut state.send_wakers);
+        wakers.append(&mut state.recv_wakers);
+        drop(state);
+        self.not_full.notify_all();
+        self.not_empty.notify_all();
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().queue.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.lock().queue.is_full()
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T, N>> {
+        // A panic while holding the lock cannot leave the queue itself inconsistent
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push_locked(&self, mut state: MutexGuard<'_, State<T, N>>, item: T) -> Result<(), T> {
+        if state.closed {
+            return Err(item);
+        }
+        state.queue.push(item).map_err(|e| e.item())?;
+        self.notify_receivers(state);
+        Ok(())
+    }
+
+    fn notify_receivers(&self, mut state: MutexGuard<'_, State<T, N>>) {
+        let wakers = mem::take(&mut state.recv_wakers);
+        drop(state);
+        self.not_empty.notify_one();
+        wakers.into_iter().for_each(Waker::wake);
+    }
+
+    fn notify_senders(&self, mut state: MutexGuard<'_, State<T, N>>) {
+        let wakers = mem::take(&mut state.send_wakers);
+        drop(state);
+        self.not_full.notify_one();
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl<T, const N: usize> Default for BoundedChannel<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for BoundedChannel<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("BoundedChannel")
+            .field("queue", &state.queue)
+            .field("closed", &state.closed)
+            .finish()
+    }
+}
+
+fn register(wakers: &mut Vec<Waker>, waker: &Waker) {
+    if !wakers.iter().any(|w| w.will_wake(waker)) {
+        wakers.push(waker.clone());
+    }
+}
+
+/// Future returned by `BoundedChannel::send_async`.
+pub struct SendFuture<'a, T, const N: usize> {
+    channel: &'a BoundedChannel<T, N>,
+    item: Option<T>,
+}
+
+// The item is never pinned, it is only moved out by value
+impl<T, const N: usize> Unpin for SendFuture<'_, T, N> {}
+
+impl<T, const N: usize> Future for SendFuture<'_, T, N> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let item = self
+            .item
+            .take()
+            .expect("SendFuture polled after completion");
+        let mut state = self.channel.lock();
+        if state.closed {
+            return Poll::Ready(Err(SendError(item)));
+        }
+        if state.queue.is_full() {
+            register(&mut state.send_wakers, cx.waker());
+            drop(state);
+            self.item = Some(item);
+            return Poll::Pending;
+        }
+        let result = self.channel.push_locked(state, item).map_err(SendError);
+        Poll::Ready(result)
+    }
+}
+
+/// Future returned by `BoundedChannel::recv_async`.
+pub struct RecvFuture<'a, T, const N: usize> {
+    channel: &'a BoundedChannel<T, N>,
+}
+
+impl<T, const N: usize> Future for RecvFuture<'_, T, N> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.channel.lock();
+        if let Some(item) = state.queue.pop() {
+            self.channel.notify_senders(state);
+            return Poll::Ready(Some(item));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        register(&mut state.recv_wakers, cx.waker());
+        Poll::Pending
+    }
+}
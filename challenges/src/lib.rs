@@ -1,2 +1,16 @@
+// Lets `#[derive(Serializable)]`, which names `::challenges`, work inside this crate too
+extern crate self as challenges;
+
+pub mod bounded_channel;
+pub mod serialization;
+pub mod small_vector;
+pub mod spsc_queue;
+pub mod stack_bitset;
+pub mod stack_heap;
+pub mod stack_lru;
+pub mod stack_map;
 pub mod stack_queue;
+pub mod stack_set;
+pub mod stack_slab;
+pub mod stack_string;
 pub mod stack_vector;
@@ -0,0 +1,809 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::io::{self, Read, Write};
+
+use crate::stack_queue::StackQueue;
+use crate::stack_vector::StackVector;
+
+pub use serializable_derive::Serializable;
+
+/// Compact binary encoding, streamed through a `Serializer` over any `io::Write`
+/// and read back through a `Deserializer` over any `io::Read`.
+///
+/// Wire layout, with every multi-byte number in the cursor's `Endian`:
+///
+/// - integers and floats: their fixed-width bytes
+/// - `bool`: one byte, 0 or 1
+/// - `char`: its scalar value as a `u32`
+/// - `String`: `u32` byte length, then the UTF-8 bytes
+/// - `Vec<T>`, `StackVector`, `StackQueue` (front to back) and `HashSet`:
+///   `u32` element count, then each element
+/// - `HashMap` and `BTreeMap`: `u32` entry count, then each key followed by its value
+/// - `Option<T>`: one byte, 0 for `None` or 1 followed by the value
+/// - `[T; N]` and tuples: each element in order, without a count
+/// - `Box<T>`: the same bytes as `T`
+///
+/// `to_bytes`/`to_writer` put a header in front of the value: the `MAGIC`
+/// bytes, the `VERSION` byte and an endianness byte (0 little, 1 big).
+///
+/// Malformed input is reported as a `DecodeError`; decoding never panics.
+/// Impls that can contain themselves must decode through `Deserializer::nested`
+/// (derived impls and the container impls here do), so that deeply nested input
+/// fails with `DepthLimitExceeded` instead of overflowing the stack.
+pub trait Serializable {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()>;
+    fn custom_deserialize<R: Read>(deserializer: &mut Deserializer<R>) -> Result<Self, DecodeError>
+    where
+        Self: Sized;
+}
+
+/// Why a value could not be decoded. Offsets count bytes from the start of the stream.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The input ended inside a fixed-width value starting at `offset`.
+    UnexpectedEof {
+        offset: usize,
+    },
+    /// A byte length prefix declared more bytes than the input holds.
+    LengthMismatch {
+        declared: usize,
+        available: usize,
+    },
+    /// A string starting at `offset` is not valid UTF-8.
+    InvalidUtf8 {
+        offset: usize,
+    },
+    /// `from_bytes` decoded a value but `count` bytes were left over.
+    TrailingBytes {
+        count: usize,
+    },
+    /// The input ended after `read` of `declared` elements.
+    CountMismatch {
+        declared: usize,
+        read: usize,
+    },
+    /// An enum tag at `offset` does not name any variant.
+    UnknownVariant {
+        offset: usize,
+        tag: u32,
+    },
+    /// A `bool` byte at `offset` is neither 0 nor 1.
+    InvalidBool {
+        offset: usize,
+        byte: u8,
+    },
+    /// A `char` at `offset` is not a Unicode scalar value.
+    InvalidChar {
+        offset: usize,
+        value: u32,
+    },
+    /// A map or set key starting at `offset` was already decoded.
+    DuplicateKey {
+        offset: usize,
+    },
+    /// A fixed-capacity container declared more elements than it can hold.
+    CapacityExceeded {
+        declared: usize,
+        capacity: usize,
+    },
+    /// A value starting at `offset` is nested deeper than `Deserializer::max_depth`.
+    DepthLimitExceeded {
+        offset: usize,
+    },
+    /// The stream does not start with `MAGIC`.
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    InvalidEndian(u8),
+    /// The reader failed for a reason other than running out of input.
+    Io(io::Error),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof { offset } => {
+                write!(f, "Unexpected end of input at byte {}", offset)
+            }
+            DecodeError::LengthMismatch {
+                declared,
+                available,
+            } => write!(
+                f,
+                "Declared length {} but only {} bytes are available",
+                declared, available
+            ),
+            DecodeError::InvalidUtf8 { offset } => write!(f, "Invalid UTF-8 at byte {}", offset),
+            DecodeError::TrailingBytes { count } => {
+                write!(f, "{} trailing bytes after value", count)
+            }
+            DecodeError::CountMismatch { declared, read } => write!(
+                f,
+                "Declared {} elements but input ended after {}",
+                declared, read
+            ),
+            DecodeError::UnknownVariant { offset, tag } => {
+                write!(f, "Unknown variant tag {} at byte {}", tag, offset)
+            }
+            DecodeError::InvalidBool { offset, byte } => {
+                write!(f, "Invalid bool byte {} at byte {}", byte, offset)
+            }
+            DecodeError::InvalidChar { offset, value } => {
+                write!(f, "Invalid char {:#x} at byte {}", value, offset)
+            }
+            DecodeError::DuplicateKey { offset } => write!(f, "Duplicate key at byte {}", offset),
+            DecodeError::CapacityExceeded { declared, capacity } => write!(
+                f,
+                "Declared {} elements but the capacity is {}",
+                declared, capacity
+            ),
+            DecodeError::DepthLimitExceeded { offset } => {
+                write!(f, "Nesting depth limit exceeded at byte {}", offset)
+            }
+            DecodeError::InvalidMagic => write!(f, "Missing magic header"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported format version {}", version)
+            }
+            DecodeError::InvalidEndian(byte) => write!(f, "Invalid endianness byte {}", byte),
+            DecodeError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Identifies a stream written by `Serializer::write_header`.
+pub const MAGIC: [u8; 4] = *b"LRSF";
+
+/// Wire format version, bumped whenever the layout changes.
+pub const VERSION: u8 = 1;
+
+/// Nesting levels a `Deserializer` accepts unless told otherwise.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Byte order of every multi-byte number on the wire.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Endian {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endian {
+    fn to_byte(self) -> u8 {
+        match self {
+            Endian::Little => 0,
+            Endian::Big => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(Endian::Little),
+            1 => Some(Endian::Big),
+            _ => None,
+        }
+    }
+}
+
+/// Writing cursor that encodes `Serializable` values into `W`.
+pub struct Serializer<W> {
+    writer: W,
+    position: usize,
+    endian: Endian,
+}
+
+impl<W: Write> Serializer<W> {
+    /// Little-endian serializer.
+    pub fn new(writer: W) -> Self {
+        Self::with_endian(writer, Endian::Little)
+    }
+
+    pub fn with_endian(writer: W, endian: Endian) -> Self {
+        Self {
+            writer,
+            position: 0,
+            endian,
+        }
+    }
+
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes the magic bytes, the format version and this serializer's endianness.
+    pub fn write_header(&mut self) -> io::Result<()> {
+        self.write_bytes(&MAGIC)?;
+        self.write_bytes(&[VERSION, self.endian.to_byte()])
+    }
+
+    pub fn serialize<T: Serializable>(&mut self, value: &T) -> io::Result<()> {
+        value.custom_serialize(self)
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.writer.write_all(bytes)?;
+        self.position += bytes.len();
+        Ok(())
+    }
+
+    /// Writes a `u32` length or count prefix.
+    pub fn write_len(&mut self, len: usize) -> io::Result<()> {
+        let len = u32::try_from(len).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "length does not fit in u32")
+        })?;
+        self.serialize(&len)
+    }
+}
+
+/// Reading cursor that decodes `Serializable` values from `R`.
+pub struct Deserializer<R> {
+    reader: R,
+    position: usize,
+    endian: Endian,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<R: Read> Deserializer<R> {
+    /// Little-endian deserializer, `read_header` switches to the stream's endianness.
+    pub fn new(reader: R) -> Self {
+        Self::with_endian(reader, Endian::Little)
+    }
+
+    pub fn with_endian(reader: R, endian: Endian) -> Self {
+        Self {
+            reader,
+            position: 0,
+            endian,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Limits how many `nested` levels may be open at once, `DEFAULT_MAX_DEPTH` by default.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// Runs `decode` one nesting level deeper.
+    ///
+    /// Every impl that can contain itself decodes through here, so crafted input
+    /// fails with `DepthLimitExceeded` instead of overflowing the stack.
+    pub fn nested<T, F>(&mut self, decode: F) -> Result<T, DecodeError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, DecodeError>,
+    {
+        if self.depth >= self.max_depth {
+            return Err(DecodeError::DepthLimitExceeded {
+                offset: self.position,
+            });
+        }
+        self.depth += 1;
+        let result = decode(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Number of bytes consumed so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Checks the magic bytes and version and adopts the endianness recorded in the header.
+    pub fn read_header(&mut self) -> Result<(), DecodeError> {
+        if self.read_array()? != MAGIC {
+            return Err(DecodeError::InvalidMagic);
+        }
+        let [version, endian] = self.read_array()?;
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+        self.endian = Endian::from_byte(endian).ok_or(DecodeError::InvalidEndian(endian))?;
+        Ok(())
+    }
+
+    pub fn deserialize<T: Serializable>(&mut self) -> Result<T, DecodeError> {
+        T::custom_deserialize(self)
+    }
+
+    /// Decodes element `index` of a sequence that declared `declared` elements.
+    ///
+    /// Input that ends before any byte of the element is reported as
+    /// `CountMismatch` rather than `UnexpectedEof`.
+    pub fn deserialize_element<T: Serializable>(
+        &mut self,
+        declared: usize,
+        index: usize,
+    ) -> Result<T, DecodeError> {
+        let start = self.position;
+        self.deserialize().map_err(|e| match e {
+            DecodeError::UnexpectedEof { .. } if self.position == start => {
+                DecodeError::CountMismatch {
+                    declared,
+                    read: index,
+                }
+            }
+            e => e,
+        })
+    }
+
+    /// Decodes a `T`, or returns `T::default()` if the input ends before its first byte.
+    ///
+    /// Only the end of the whole input counts, so this suits the last value of a
+    /// stream. Anywhere else the bytes of the next value are decoded as `T`.
+    pub fn deserialize_or_default<T: Serializable + Default>(&mut self) -> Result<T, DecodeError> {
+        let start = self.position;
+        match self.deserialize() {
+            Err(DecodeError::UnexpectedEof { .. }) if self.position == start => Ok(T::default()),
+            result => result,
+        }
+    }
+
+    /// Fills `buffer`, counting partially read bytes in `position` if the input runs out.
+    pub fn read_bytes(&mut self, buffer: &mut [u8]) -> Result<(), DecodeError> {
+        let offset = self.position;
+        let mut filled = 0;
+        while filled < buffer.len() {
+            match self.reader.read(&mut buffer[filled..]) {
+                Ok(0) => {
+                    self.position += filled;
+                    return Err(DecodeError::UnexpectedEof { offset });
+                }
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.position += filled;
+                    return Err(DecodeError::Io(e));
+                }
+            }
+        }
+        self.position += filled;
+        Ok(())
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut buffer = [0; N];
+        self.read_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Reads a `u32` length or count prefix.
+    pub fn read_len(&mut self) -> Result<usize, DecodeError> {
+        Ok(self.deserialize::<u32>()? as usize)
+    }
+
+    /// Reads `len` bytes without trusting `len` for the allocation size.
+    pub fn read_byte_vec(&mut self, len: usize) -> Result<Vec<u8>, DecodeError> {
+        let mut bytes = Vec::new();
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(DecodeError::Io)?;
+        self.position += bytes.len();
+        if bytes.len() < len {
+            return Err(DecodeError::LengthMismatch {
+                declared: len,
+                available: bytes.len(),
+            });
+        }
+        Ok(bytes)
+    }
+}
+
+/// Encodes `value` behind a little-endian header into a fresh buffer.
+pub fn to_bytes<T: Serializable>(value: &T) -> io::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    to_writer(&mut buffer, value)?;
+    Ok(buffer)
+}
+
+/// Encodes `value` behind a little-endian header, use `Serializer::with_endian` for big-endian.
+pub fn to_writer<T: Serializable, W: Write>(writer: W, value: &T) -> io::Result<()> {
+    let mut serializer = Serializer::new(writer);
+    serializer.write_header()?;
+    serializer.serialize(value)
+}
+
+/// Decodes a header and a single value that must span all of `bytes`.
+pub fn from_bytes<T: Serializable>(bytes: &[u8]) -> Result<T, DecodeError> {
+    let mut deserializer = Deserializer::new(bytes);
+    deserializer.read_header()?;
+    let value = deserializer.deserialize()?;
+    if deserializer.position() != bytes.len() {
+        return Err(DecodeError::TrailingBytes {
+            count: bytes.len() - deserializer.position(),
+        });
+    }
+    Ok(value)
+}
+
+/// Decodes a header and a single value, leaving the rest of the stream unread.
+pub fn from_reader<T: Serializable, R: Read>(reader: R) -> Result<T, DecodeError> {
+    let mut deserializer = Deserializer::new(reader);
+    deserializer.read_header()?;
+    deserializer.deserialize()
+}
+
+macro_rules! impl_serializable_for_primitive {
+    ($t:ty) => {
+        impl Serializable for $t {
+            fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+                let bytes = match serializer.endian() {
+                    Endian::Little => self.to_le_bytes(),
+                    Endian::Big => self.to_be_bytes(),
+                };
+                serializer.write_bytes(&bytes)
+            }
+
+            fn custom_deserialize<R: Read>(
+                deserializer: &mut Deserializer<R>,
+            ) -> Result<Self, DecodeError> {
+                let bytes = deserializer.read_array()?;
+                Ok(match deserializer.endian() {
+                    Endian::Little => <$t>::from_le_bytes(bytes),
+                    Endian::Big => <$t>::from_be_bytes(bytes),
+                })
+            }
+        }
+    };
+}
+
+// Implementation of Serializable Trait for primitive types
+impl_serializable_for_primitive!(i8);
+impl_serializable_for_primitive!(u8);
+impl_serializable_for_primitive!(i16);
+impl_serializable_for_primitive!(u16);
+impl_serializable_for_primitive!(i32);
+impl_serializable_for_primitive!(u32);
+impl_serializable_for_primitive!(i64);
+impl_serializable_for_primitive!(u64);
+impl_serializable_for_primitive!(f32);
+impl_serializable_for_primitive!(f64);
+
+impl Serializable for String {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.write_len(self.len())?;
+        serializer.write_bytes(self.as_bytes())
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        let len = deserializer.read_len()?;
+        let offset = deserializer.position();
+        let bytes = deserializer.read_byte_vec(len)?;
+        String::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8 { offset })
+    }
+}
+
+impl<T: Serializable> Serializable for Vec<T> {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.write_len(self.len())?;
+        for item in self {
+            item.custom_serialize(serializer)?;
+        }
+        Ok(())
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| {
+            let len = deserializer.read_len()?;
+            // Grow as items arrive, the count alone is not trusted for the allocation
+            let mut vec = Vec::new();
+            for index in 0..len {
+                vec.push(deserializer.deserialize_element(len, index)?);
+            }
+            Ok(vec)
+        })
+    }
+}
+
+impl Serializable for bool {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.write_bytes(&[*self as u8])
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        let offset = deserializer.position();
+        match deserializer.read_array()? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            [byte] => Err(DecodeError::InvalidBool { offset, byte }),
+        }
+    }
+}
+
+impl Serializable for char {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.serialize(&u32::from(*self))
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        let offset = deserializer.position();
+        let value = deserializer.deserialize()?;
+        char::from_u32(value).ok_or(DecodeError::InvalidChar { offset, value })
+    }
+}
+
+impl<T: Serializable> Serializable for Option<T> {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        match self {
+            None => serializer.write_bytes(&[0]),
+            Some(value) => {
+                serializer.write_bytes(&[1])?;
+                serializer.serialize(value)
+            }
+        }
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| {
+            let offset = deserializer.position();
+            match deserializer.read_array()? {
+                [0] => Ok(None),
+                [1] => Ok(Some(deserializer.deserialize()?)),
+                [tag] => Err(DecodeError::UnknownVariant {
+                    offset,
+                    tag: tag.into(),
+                }),
+            }
+        })
+    }
+}
+
+impl<T: Serializable> Serializable for Box<T> {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.serialize(&**self)
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| Ok(Box::new(deserializer.deserialize()?)))
+    }
+}
+
+impl<T: Serializable, const N: usize> Serializable for [T; N] {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        for item in self {
+            serializer.serialize(item)?;
+        }
+        Ok(())
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| {
+            let mut items = Vec::with_capacity(N);
+            for index in 0..N {
+                items.push(deserializer.deserialize_element(N, index)?);
+            }
+            match items.try_into() {
+                Ok(array) => Ok(array),
+                Err(_) => unreachable!("exactly N elements were decoded"),
+            }
+        })
+    }
+}
+
+macro_rules! impl_serializable_for_tuple {
+    ($($name:ident $index:tt),+) => {
+        impl<$($name: Serializable),+> Serializable for ($($name,)+) {
+            fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+                $(serializer.serialize(&self.$index)?;)+
+                Ok(())
+            }
+
+            fn custom_deserialize<R: Read>(
+                deserializer: &mut Deserializer<R>,
+            ) -> Result<Self, DecodeError> {
+                Ok(($(deserializer.deserialize::<$name>()?,)+))
+            }
+        }
+    };
+}
+
+impl_serializable_for_tuple!(A 0);
+impl_serializable_for_tuple!(A 0, B 1);
+impl_serializable_for_tuple!(A 0, B 1, C 2);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_serializable_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+impl<K, V, S> Serializable for HashMap<K, V, S>
+where
+    K: Serializable + Eq + Hash,
+    V: Serializable,
+    S: BuildHasher + Default,
+{
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.write_len(self.len())?;
+        for (key, value) in self {
+            serializer.serialize(key)?;
+            serializer.serialize(value)?;
+        }
+        Ok(())
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| {
+            let len = deserializer.read_len()?;
+            let mut map = HashMap::with_hasher(S::default());
+            for index in 0..len {
+                let offset = deserializer.position();
+                let key = deserializer.deserialize_element(len, index)?;
+                let value = deserializer.deserialize()?;
+                if map.insert(key, value).is_some() {
+                    return Err(DecodeError::DuplicateKey { offset });
+                }
+            }
+            Ok(map)
+        })
+    }
+}
+
+impl<K: Serializable + Ord, V: Serializable> Serializable for BTreeMap<K, V> {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.write_len(self.len())?;
+        for (key, value) in self {
+            serializer.serialize(key)?;
+            serializer.serialize(value)?;
+        }
+        Ok(())
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| {
+            let len = deserializer.read_len()?;
+            let mut map = BTreeMap::new();
+            for index in 0..len {
+                let offset = deserializer.position();
+                let key = deserializer.deserialize_element(len, index)?;
+                let value = deserializer.deserialize()?;
+                if map.insert(key, value).is_some() {
+                    return Err(DecodeError::DuplicateKey { offset });
+                }
+            }
+            Ok(map)
+        })
+    }
+}
+
+impl<T, S> Serializable for HashSet<T, S>
+where
+    T: Serializable + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.write_len(self.len())?;
+        for item in self {
+            serializer.serialize(item)?;
+        }
+        Ok(())
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| {
+            let len = deserializer.read_len()?;
+            let mut set = HashSet::with_hasher(S::default());
+            for index in 0..len {
+                let offset = deserializer.position();
+                if !set.insert(deserializer.deserialize_element(len, index)?) {
+                    return Err(DecodeError::DuplicateKey { offset });
+                }
+            }
+            Ok(set)
+        })
+    }
+}
+
+impl<T: Serializable, const N: usize> Serializable for StackVector<T, N> {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.write_len(self.len())?;
+        for item in self {
+            serializer.serialize(item)?;
+        }
+        Ok(())
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| {
+            let len = read_len_within(deserializer, N)?;
+            let mut vector = StackVector::new();
+            for index in 0..len {
+                vector.push(deserializer.deserialize_element(len, index)?);
+            }
+            Ok(vector)
+        })
+    }
+}
+
+impl<T: Serializable, const N: usize> Serializable for StackQueue<T, N> {
+    fn custom_serialize<W: Write>(&self, serializer: &mut Serializer<W>) -> io::Result<()> {
+        serializer.write_len(self.len())?;
+        for item in self.iter() {
+            serializer.serialize(item)?;
+        }
+        Ok(())
+    }
+
+    fn custom_deserialize<R: Read>(
+        deserializer: &mut Deserializer<R>,
+    ) -> Result<Self, DecodeError> {
+        deserializer.nested(|deserializer| {
+            let len = read_len_within(deserializer, N)?;
+            let mut queue = StackQueue::new();
+            for index in 0..len {
+                queue
+                    .push_back(deserializer.deserialize_element(len, index)?)
+                    .expect("length was checked against the capacity");
+            }
+            Ok(queue)
+        })
+    }
+}
+
+/// Reads a count prefix for a container that holds at most `capacity` elements.
+fn read_len_within<R: Read>(
+    deserializer: &mut Deserializer<R>,
+    capacity: usize,
+) -> Result<usize, DecodeError> {
+    let declared = deserializer.read_len()?;
+    if declared > capacity {
+        return Err(DecodeError::CapacityExceeded { declared, capacity });
+    }
+    Ok(declared)
+}
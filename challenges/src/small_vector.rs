@@ -0,0 +1,360 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::slice::{self, SliceIndex};
+use std::vec;
+
+use crate::stack_vector::{StackVector, StackVectorIntoIterator};
+
+enum Storage<T, const N: usize> {
+    Inline(StackVector<T, N>),
+    Heap(Vec<T>),
+}
+
+/// Vector that keeps up to `N` elements inline in a `StackVector` and moves
+/// them into a heap `Vec` once it grows past that.
+pub struct SmallVector<T, const N: usize> {
+    storage: Storage<T, N>,
+}
+
+impl<T, const N: usize> SmallVector<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            storage: Storage::Inline(StackVector::new()),
+        }
+    }
+
+    /// Returns `true` if the elements live on the heap.
+    pub fn spilled(&self) -> bool {
+        matches!(self.storage, Storage::Heap(_))
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline(inline) => inline.len(),
+            Storage::Heap(heap) => heap.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of elements the current storage holds without reallocating.
+    pub fn capacity(&self) -> usize {
+        match &self.storage {
+            Storage::Inline(_) => N,
+            Storage::Heap(heap) => heap.capacity(),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        match &self.storage {
+            Storage::Inline(inline) => inline.as_slice(),
+            Storage::Heap(heap) => heap.as_slice(),
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.storage {
+            Storage::Inline(inline) => inline.as_mut_slice(),
+            Storage::Heap(heap) => heap.as_mut_slice(),
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        match &mut self.storage {
+            Storage::Inline(inline) => {
+                if let Err(e) = inline.try_push(value) {
+                    self.heap_mut(1).push(e.element());
+                }
+            }
+            Storage::Heap(heap) => heap.push(value),
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.storage {
+            Storage::Inline(inline) => inline.pop(),
+            Storage::Heap(heap) => heap.pop(),
+        }
+    }
+
+    /// Inserts `value` at `index`, spilling to the heap if the inline storage is full.
+    pub fn insert(&mut self, index: usize, value: T) {
+        match &mut self.storage {
+            Storage::Inline(inline) => {
+                if let Err(e) = inline.try_insert(index, value) {
+                    self.heap_mut(1).insert(index, e.element());
+                }
+            }
+            Storage::Heap(heap) => heap.insert(index, value),
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        match &mut self.storage {
+            Storage::Inline(inline) => inline.remove(index),
+            Storage::Heap(heap) => heap.remove(index),
+        }
+    }
+
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        match &mut self.storage {
+            Storage::Inline(inline) => inline.swap_remove(index),
+            Storage::Heap(heap) => heap.swap_remove(index),
+        }
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        match &mut self.storage {
+            Storage::Inline(inline) => inline.truncate(len),
+            Storage::Heap(heap) => heap.truncate(len),
+        }
+    }
+
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        match &mut self.storage {
+            Storage::Inline(inline) => inline.retain(f),
+            Storage::Heap(heap) => heap.retain(f),
+        }
+    }
+
+    /// Drops every element. Spilled storage stays on the heap, see `shrink_to_inline`.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Moves the elements back inline if they fit, returning whether the vector is now inline.
+    pub fn shrink_to_inline(&mut self) -> bool {
+        if let Storage::Heap(heap) = &mut self.storage {
+            if heap.len() > N {
+                return false;
+            }
+            let inline = heap.drain(..).collect();
+            self.storage = Storage::Inline(inline);
+        }
+        true
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Moves the elements to the heap if needed, reserving room for `additional` more.
+    fn heap_mut(&mut self, additional: usize) -> &mut Vec<T> {
+        if let Storage::Inline(inline) = &mut self.storage {
+            let mut heap = Vec::with_capacity((inline.len() + additional).max(2 * N));
+            heap.extend(inline.drain(..));
+            self.storage = Storage::Heap(heap);
+        }
+        match &mut self.storage {
+            Storage::Heap(heap) => heap,
+            Storage::Inline(_) => unreachable!(),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallVector<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for SmallVector<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallVector<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for SmallVector<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for SmallVector<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const N: usize> Index<I> for SmallVector<T, N> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(self.as_slice(), index)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const N: usize> IndexMut<I> for SmallVector<T, N> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(self.as_mut_slice(), index)
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for SmallVector<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVector<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for SmallVector<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for SmallVector<T, N> {}
+
+impl<T: Hash, const N: usize> Hash for SmallVector<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.as_slice(), state)
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallVector<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        if self.len() + lower > N {
+            self.heap_mut(lower);
+        }
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallVector<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = Self::new();
+        vector.extend(iter);
+        vector
+    }
+}
+
+impl<T, const N: usize> From<Vec<T>> for SmallVector<T, N> {
+    /// Keeps the allocation, call `shrink_to_inline` to move short vectors inline.
+    fn from(vec: Vec<T>) -> Self {
+        Self {
+            storage: Storage::Heap(vec),
+        }
+    }
+}
+
+impl<T, const N: usize> From<StackVector<T, N>> for SmallVector<T, N> {
+    fn from(vector: StackVector<T, N>) -> Self {
+        Self {
+            storage: Storage::Inline(vector),
+        }
+    }
+}
+
+impl<T, const N: usize> From<SmallVector<T, N>> for Vec<T> {
+    fn from(vector: SmallVector<T, N>) -> Self {
+        match vector.storage {
+            Storage::Inline(inline) => inline.into(),
+            Storage::Heap(heap) => heap,
+        }
+    }
+}
+
+/// By-value iterator over a `SmallVector`.
+pub enum IntoIter<T, const N: usize> {
+    Inline(StackVectorIntoIterator<T, N>),
+    Heap(vec::IntoIter<T>),
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    /// Returns the elements that have not been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            IntoIter::Inline(iter) => iter.as_slice(),
+            IntoIter::Heap(iter) => iter.as_slice(),
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            IntoIter::Inline(iter) => iter.next(),
+            IntoIter::Heap(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            IntoIter::Inline(iter) => iter.size_hint(),
+            IntoIter::Heap(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            IntoIter::Inline(iter) => iter.next_back(),
+            IntoIter::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> IntoIterator for SmallVector<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self.storage {
+            Storage::Inline(inline) => IntoIter::Inline(inline.into_iter()),
+            Storage::Heap(heap) => IntoIter::Heap(heap.into_iter()),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallVector<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallVector<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,244 @@
+use std::cell::{Cell, UnsafeCell};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::stack_queue::QueueFullError;
+
+/// Lock-free single-producer/single-consumer ring buffer with fixed capacity.
+///
+/// Indices run over `[0, 2 * N)` so a full queue can be told apart from an
+/// empty one without wasting a slot. Use `split` to get the two handles.
+pub struct SpscQueue<T, const N: usize> {
+    data: [UnsafeCell<MaybeUninit<T>>; N],
+    head: AtomicUsize,
+    tail: AtomicUsize,
+}
+
+// SAFETY: Slots are only accessed through the single `Producer` and single
+// `Consumer`, which synchronize ownership of each slot via `head` and `tail`
+unsafe impl<T: Send, const N: usize> Sync for SpscQueue<T, N> {}
+
+impl<T, const N: usize> SpscQueue<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            data: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the queue into a producer and a consumer handle that can live on different threads.
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        (
+            Producer {
+                queue: self,
+                _not_sync: PhantomData,
+            },
+            Consumer {
+                queue: self,
+                _not_sync: PhantomData,
+            },
+        )
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Number of elements at the time of the call, may be stale once it returns.
+    pub fn len(&self) -> usize {
+        Self::distance(
+            self.head.load(Ordering::Acquire),
+            self.tail.load(Ordering::Acquire),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    #[inline(always)]
+    fn distance(head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            tail + 2 * N - head
+        }
+    }
+
+    #[inline(always)]
+    fn increment(index: usize) -> usize {
+        if index + 1 == 2 * N {
+            0
+        } else {
+            index + 1
+        }
+    }
+
+    #[inline(always)]
+    fn slot(&self, index: usize) -> *mut MaybeUninit<T> {
+        let index = if index >= N { index - N } else { index };
+        self.data[index].get()
+    }
+}
+
+impl<T, const N: usize> Default for SpscQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for SpscQueue<T, N> {
+    fn drop(&mut self) {
+        let mut head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        while head != tail {
+            // SAFETY: Slots in `[head, tail)` are initialized and no handles are alive
+            unsafe { (*self.slot(head)).assume_init_drop() };
+            head = Self::increment(head);
+        }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for SpscQueue<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscQueue")
+            .field("len", &self.len())
+            .field("capacity", &N)
+            .finish()
+    }
+}
+
+/// Writing half of an `SpscQueue`.
+pub struct Producer<'a, T, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+// SAFETY: The producer owns the free slots and only hands `T` values across threads
+unsafe impl<T: Send, const N: usize> Send for Producer<'_, T, N> {}
+
+impl<T, const N: usize> Producer<'_, T, N> {
+    pub fn push(&mut self, item: T) -> Result<(), QueueFullError<T>> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        if SpscQueue::<T, N>::distance(head, tail) >= N {
+            return Err(QueueFullError::new(item));
+        }
+        // SAFETY: The slot at `tail` is free and only the producer writes to it
+        unsafe { (*self.queue.slot(tail)).write(item) };
+        self.queue
+            .tail
+            .store(SpscQueue::<T, N>::increment(tail), Ordering::Release);
+        Ok(())
+    }
+
+    /// Clones as many items from `items` as fit and returns how many were pushed.
+    pub fn push_slice(&mut self, items: &[T]) -> usize
+    where
+        T: Clone,
+    {
+        let mut tail = self.queue.tail.load(Ordering::Relaxed);
+        let head = self.queue.head.load(Ordering::Acquire);
+        let count = items.len().min(N - SpscQueue::<T, N>::distance(head, tail));
+        for item in &items[..count] {
+            // SAFETY: The first `count` slots from `tail` are free
+            unsafe { (*self.queue.slot(tail)).write(item.clone()) };
+            tail = SpscQueue::<T, N>::increment(tail);
+            // Publish per element so a panicking `clone` cannot hide written items
+            self.queue.tail.store(tail, Ordering::Release);
+        }
+        count
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.queue.is_full()
+    }
+}
+
+/// Reading half of an `SpscQueue`.
+///
+/// The handle can be sent to another thread but not shared, since `peek` hands
+/// out `&T` and `T` need not be `Sync`:
+///
+/// ```compile_fail
+/// use challenges::spsc_queue::Consumer;
+/// use std::cell::Cell;
+///
+/// fn assert_sync<S: Sync>() {}
+/// assert_sync::<Consumer<'static, Cell<u32>, 4>>();
+/// ```
+pub struct Consumer<'a, T, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+// SAFETY: The consumer owns the published slots and only hands `T` values across threads
+unsafe impl<T: Send, const N: usize> Send for Consumer<'_, T, N> {}
+
+impl<T, const N: usize> Consumer<'_, T, N> {
+    pub fn pop(&mut self) -> Option<T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        // SAFETY: The slot at `head` was published by the producer and only the consumer reads it
+        let item = unsafe { (*self.queue.slot(head)).assume_init_read() };
+        self.queue
+            .head
+            .store(SpscQueue::<T, N>::increment(head), Ordering::Release);
+        Some(item)
+    }
+
+    /// Moves up to `out.len()` items into `out`, dropping its previous contents, and
+    /// returns how many were written.
+    pub fn pop_into(&mut self, out: &mut [T]) -> usize {
+        let mut head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        let count = out.len().min(SpscQueue::<T, N>::distance(head, tail));
+        for target in &mut out[..count] {
+            // SAFETY: The first `count` slots from `head` were published by the producer
+            let item = unsafe { (*self.queue.slot(head)).assume_init_read() };
+            head = SpscQueue::<T, N>::increment(head);
+            // Release the slot before the old value is dropped, in case its destructor panics
+            self.queue.head.store(head, Ordering::Release);
+            *target = item;
+        }
+        count
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        let tail = self.queue.tail.load(Ordering::Acquire);
+        if head == tail {
+            None
+        } else {
+            // SAFETY: The producer never touches a published slot until the consumer releases it
+            unsafe { Some((*self.queue.slot(head)).assume_init_ref()) }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
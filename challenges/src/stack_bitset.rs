@@ -0,0 +1,327 @@
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Number of `u64` words needed to hold `bits` bits.
+pub const fn words_for(bits: usize) -> usize {
+    bits.div_ceil(WORD_BITS)
+}
+
+/// Names the `StackBitSet` type holding `bits` bits, filling in the word count.
+///
+/// ```
+/// use challenges::stack_bitset;
+///
+/// let mut flags = <stack_bitset!(100)>::new();
+/// flags.set(99);
+/// let copy: stack_bitset!(100) = flags;
+/// assert_eq!(copy.count_ones(), 1);
+/// ```
+#[macro_export]
+macro_rules! stack_bitset {
+    ($bits:expr) => {
+        $crate::stack_bitset::StackBitSet<{ $bits }, { $crate::stack_bitset::words_for($bits) }>
+    };
+}
+
+/// Fixed-size set of bit indices in `[0, BITS)`, packed into `u64` words.
+///
+/// Stable Rust cannot derive an array length from `BITS`, so the word count is
+/// a second parameter that must equal `words_for(BITS)`, e.g.
+/// `StackBitSet<100, { words_for(100) }>`. A mismatch fails to compile.
+/// `stack_bitset!(100)` spells out the same type without repeating the size.
+/// Bits past `BITS` in the last word are always zero.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StackBitSet<const BITS: usize, const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+impl<const BITS: usize, const WORDS: usize> StackBitSet<BITS, WORDS> {
+    pub const fn new() -> Self {
+        const { assert!(WORDS == words_for(BITS), "WORDS must equal words_for(BITS)") };
+        Self { words: [0; WORDS] }
+    }
+
+    /// Number of bits the set can hold.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        BITS
+    }
+
+    /// Panics if `index >= BITS`.
+    pub fn set(&mut self, index: usize) {
+        let (word, mask) = Self::locate(index);
+        self.words[word] |= mask;
+    }
+
+    /// Panics if `index >= BITS`.
+    pub fn clear(&mut self, index: usize) {
+        let (word, mask) = Self::locate(index);
+        self.words[word] &= !mask;
+    }
+
+    /// Panics if `index >= BITS`.
+    pub fn toggle(&mut self, index: usize) {
+        let (word, mask) = Self::locate(index);
+        self.words[word] ^= mask;
+    }
+
+    /// Panics if `index >= BITS`.
+    pub fn test(&self, index: usize) -> bool {
+        let (word, mask) = Self::locate(index);
+        self.words[word] & mask != 0
+    }
+
+    pub fn set_all(&mut self) {
+        self.words = [u64::MAX; WORDS];
+        self.mask_tail();
+    }
+
+    pub fn clear_all(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn count_zeros(&self) -> usize {
+        BITS - self.count_ones()
+    }
+
+    /// Indices of the set bits in ascending order.
+    pub fn iter_ones(&self) -> IterOnes<'_> {
+        IterOnes {
+            words: &self.words,
+            word_index: 0,
+            current: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    /// Number of set bits strictly below `index`. Panics if `index > BITS`.
+    pub fn rank(&self, index: usize) -> usize {
+        assert!(index <= BITS, "index out of bounds");
+        let (full_words, rest) = (index / WORD_BITS, index % WORD_BITS);
+        let mut count: usize = self.words[..full_words]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+        if rest != 0 {
+            count += (self.words[full_words] & ((1 << rest) - 1)).count_ones() as usize;
+        }
+        count
+    }
+
+    /// Index of the set bit with rank `n`, i.e. the `n`-th set bit counting from zero.
+    pub fn select(&self, mut n: usize) -> Option<usize> {
+        for (word_index, &word) in self.words.iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if n < ones {
+                let mut word = word;
+                // Drop the lowest `n` set bits, the answer is then the lowest remaining one
+                for _ in 0..n {
+                    word &= word - 1;
+                }
+                return Some(word_index * WORD_BITS + word.trailing_zeros() as usize);
+            }
+            n -= ones;
+        }
+        None
+    }
+
+    /// Raw words, bit `i` lives in bit `i % 64` of word `i / 64`.
+    pub fn as_words(&self) -> &[u64; WORDS] {
+        &self.words
+    }
+
+    #[inline(always)]
+    fn locate(index: usize) -> (usize, u64) {
+        assert!(index < BITS, "index out of bounds");
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+
+    fn mask_tail(&mut self) {
+        let rest = BITS % WORD_BITS;
+        if rest != 0 {
+            self.words[WORDS - 1] &= (1 << rest) - 1;
+        }
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> Default for StackBitSet<BITS, WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> fmt::Debug for StackBitSet<BITS, WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter_ones()).finish()
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> BitOrAssign for StackBitSet<BITS, WORDS> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        for (word, other) in self.words.iter_mut().zip(rhs.words) {
+            *word |= other;
+        }
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> BitAndAssign for StackBitSet<BITS, WORDS> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        for (word, other) in self.words.iter_mut().zip(rhs.words) {
+            *word &= other;
+        }
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> SubAssign for StackBitSet<BITS, WORDS> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (word, other) in self.words.iter_mut().zip(rhs.words) {
+            *word &= !other;
+        }
+    }
+}
+
+/// Union.
+impl<const BITS: usize, const WORDS: usize> BitOr for StackBitSet<BITS, WORDS> {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self {
+        self |= rhs;
+        self
+    }
+}
+
+/// Intersection.
+impl<const BITS: usize, const WORDS: usize> BitAnd for StackBitSet<BITS, WORDS> {
+    type Output = Self;
+
+    fn bitand(mut self, rhs: Self) -> Self {
+        self &= rhs;
+        self
+    }
+}
+
+/// Difference.
+impl<const BITS: usize, const WORDS: usize> Sub for StackBitSet<BITS, WORDS> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self {
+        self -= rhs;
+        self
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> Extend<usize> for StackBitSet<BITS, WORDS> {
+    /// Panics if an index is out of range, like `set`.
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for index in iter {
+            self.set(index);
+        }
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize> FromIterator<usize> for StackBitSet<BITS, WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// Iterator over the set bits of a `StackBitSet`.
+#[derive(Clone)]
+pub struct IterOnes<'a> {
+    words: &'a [u64],
+    word_index: usize,
+    /// Bits of the current word that have not been yielded yet.
+    current: u64,
+}
+
+impl Iterator for IterOnes<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.word_index += 1;
+            self.current = *self.words.get(self.word_index)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.word_index * WORD_BITS + bit)
+    }
+}
+
+impl FusedIterator for IterOnes<'_> {}
+
+impl<'a, const BITS: usize, const WORDS: usize> IntoIterator for &'a StackBitSet<BITS, WORDS> {
+    type Item = usize;
+    type IntoIter = IterOnes<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_ones()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::{StackBitSet, WORD_BITS};
+    use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Unexpected, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::fmt;
+
+    /// Serialized as the sequence of `WORDS` raw words.
+    impl<const BITS: usize, const WORDS: usize> Serialize for StackBitSet<BITS, WORDS> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.words.iter())
+        }
+    }
+
+    impl<'de, const BITS: usize, const WORDS: usize> Deserialize<'de> for StackBitSet<BITS, WORDS> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct StackBitSetVisitor<const BITS: usize, const WORDS: usize>;
+
+            impl<'de, const BITS: usize, const WORDS: usize> Visitor<'de> for StackBitSetVisitor<BITS, WORDS> {
+                type Value = StackBitSet<BITS, WORDS>;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a sequence of {} words holding {} bits", WORDS, BITS)
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut set = StackBitSet::new();
+                    for index in 0..WORDS {
+                        set.words[index] = seq
+                            .next_element()?
+                            .ok_or_else(|| A::Error::invalid_length(index, &self))?;
+                    }
+                    if seq.next_element::<u64>()?.is_some() {
+                        return Err(A::Error::invalid_length(WORDS + 1, &self));
+                    }
+                    let last = set.words.last().copied().unwrap_or(0);
+                    let rest = BITS % WORD_BITS;
+                    if rest != 0 && last >> rest != 0 {
+                        return Err(A::Error::invalid_value(
+                            Unexpected::Unsigned(last),
+                            &"no bits set past the capacity",
+                        ));
+                    }
+                    Ok(set)
+                }
+            }
+
+            deserializer.deserialize_seq(StackBitSetVisitor::<BITS, WORDS>)
+        }
+    }
+}
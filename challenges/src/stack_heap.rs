@@ -0,0 +1,297 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use crate::stack_vector::{CapacityError, StackVector};
+
+/// Ordering used by a `StackHeap`; the element that compares greatest sits on top.
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Largest element on top, the default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MaxHeap;
+
+/// Smallest element on top.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinHeap;
+
+impl<T: Ord> Compare<T> for MaxHeap {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T: Ord> Compare<T> for MinHeap {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T, F> Compare<T> for F
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// Fixed-capacity binary heap stored in a `StackVector`.
+pub struct StackHeap<T, const N: usize, C = MaxHeap> {
+    data: StackVector<T, N>,
+    comparator: C,
+}
+
+impl<T: Ord, const N: usize> StackHeap<T, N> {
+    pub const fn new() -> Self {
+        Self::with_comparator(MaxHeap)
+    }
+}
+
+impl<T: Ord, const N: usize> StackHeap<T, N, MinHeap> {
+    pub const fn new_min() -> Self {
+        Self::with_comparator(MinHeap)
+    }
+}
+
+impl<T, const N: usize, C: Compare<T>> StackHeap<T, N, C> {
+    pub const fn with_comparator(comparator: C) -> Self {
+        Self {
+            data: StackVector::new(),
+            comparator,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.data.try_push(item)?;
+        self.sift_up(self.data.len() - 1);
+        Ok(())
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.try_push(item).is_err() {
+            panic!("Capacity exceeded");
+        }
+    }
+
+    /// Pushes `item`, keeping only the best `N` elements seen so far.
+    ///
+    /// When full, the worst element is evicted if `item` beats it, otherwise
+    /// `item` itself is rejected. Either way the element that no longer fits is
+    /// returned. Finding the worst element scans the leaves, so a full push is O(N).
+    pub fn push_top_k(&mut self, item: T) -> Option<T> {
+        if !self.is_full() {
+            self.push(item);
+            return None;
+        }
+        // A zero-capacity heap is always full and has nothing to evict
+        let Some(worst) = self.worst_index() else {
+            return Some(item);
+        };
+        if self.comparator.compare(&item, &self.data[worst]) != Ordering::Greater {
+            return Some(item);
+        }
+        let evicted = std::mem::replace(&mut self.data[worst], item);
+        self.sift_up(worst);
+        Some(evicted)
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.data.len();
+        if len == 0 {
+            return None;
+        }
+        self.data.swap(0, len - 1);
+        let item = self.data.pop();
+        self.sift_down(0);
+        item
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Mutable access to the top element; the heap is repaired when the guard is dropped.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, N, C>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut {
+                heap: self,
+                modified: false,
+            })
+        }
+    }
+
+    /// Elements in arbitrary order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.as_slice().iter()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the elements in arbitrary order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data.into()
+    }
+
+    /// Returns the elements sorted in ascending order according to the comparator.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(item) = self.pop() {
+            sorted.push(item);
+        }
+        sorted.reverse();
+        sorted
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self
+                .comparator
+                .compare(&self.data[index], &self.data[parent])
+                != Ordering::Greater
+            {
+                break;
+            }
+            self.data.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        let len = self.data.len();
+        loop {
+            let left = 2 * index + 1;
+            if left >= len {
+                break;
+            }
+            let right = left + 1;
+            let mut child = left;
+            if right < len
+                && self.comparator.compare(&self.data[right], &self.data[left]) == Ordering::Greater
+            {
+                child = right;
+            }
+            if self
+                .comparator
+                .compare(&self.data[child], &self.data[index])
+                != Ordering::Greater
+            {
+                break;
+            }
+            self.data.swap(index, child);
+            index = child;
+        }
+    }
+
+    /// The worst element is always a leaf, so only the second half needs scanning.
+    fn worst_index(&self) -> Option<usize> {
+        let len = self.data.len();
+        (len / 2..len).min_by(|&a, &b| self.comparator.compare(&self.data[a], &self.data[b]))
+    }
+}
+
+impl<T: Ord, const N: usize> Default for StackHeap<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize, C: Clone> Clone for StackHeap<T, N, C> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            comparator: self.comparator.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize, C> fmt::Debug for StackHeap<T, N, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<T, const N: usize, C: Compare<T>> Extend<T> for StackHeap<T, N, C> {
+    /// Panics if the items do not fit, like `push`.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, const N: usize, C: Compare<T> + Default> FromIterator<T> for StackHeap<T, N, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::with_comparator(C::default());
+        heap.extend(iter);
+        heap
+    }
+}
+
+/// Guard returned by `StackHeap::peek_mut`.
+pub struct PeekMut<'a, T, const N: usize, C: Compare<T>> {
+    heap: &'a mut StackHeap<T, N, C>,
+    modified: bool,
+}
+
+impl<T, const N: usize, C: Compare<T>> PeekMut<'_, T, N, C> {
+    /// Removes the top element.
+    pub fn pop(mut this: Self) -> T {
+        // Popping repairs the heap itself, no sift is needed on drop
+        this.modified = false;
+        this.heap
+            .pop()
+            .expect("PeekMut is only created for non-empty heaps")
+    }
+}
+
+impl<T, const N: usize, C: Compare<T>> Deref for PeekMut<'_, T, N, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<T, const N: usize, C: Compare<T>> DerefMut for PeekMut<'_, T, N, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.modified = true;
+        &mut self.heap.data[0]
+    }
+}
+
+impl<T, const N: usize, C: Compare<T>> Drop for PeekMut<'_, T, N, C> {
+    fn drop(&mut self) {
+        if self.modified {
+            self.heap.sift_down(0);
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem::{self, MaybeUninit};
+
+/// Sentinel for a missing link.
+const NONE: usize = usize::MAX;
+
+/// Fixed-capacity least-recently-used cache.
+///
+/// Entries live densely in `[0, len)` of a fixed array and are chained from most
+/// to least recently used through `prev`/`next` indices. Lookups scan the
+/// occupied slots, so they are O(N) and only need `K: Eq`.
+pub struct StackLru<K, V, const N: usize> {
+    entries: [MaybeUninit<(K, V)>; N],
+    prev: [usize; N],
+    next: [usize; N],
+    /// Most recently used entry.
+    head: usize,
+    /// Least recently used entry.
+    tail: usize,
+    len: usize,
+    hits: u64,
+    misses: u64,
+}
+
+/// What `StackLru::put` pushed out of the cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Displaced<K, V> {
+    /// The key was already cached, this is its previous value.
+    Replaced(V),
+    /// The cache was full and this least recently used entry made room.
+    Evicted(K, V),
+}
+
+impl<K, V, const N: usize> StackLru<K, V, N> {
+    pub const fn new() -> Self {
+        Self {
+            entries: [const { MaybeUninit::uninit() }; N],
+            prev: [NONE; N],
+            next: [NONE; N],
+            head: NONE,
+            tail: NONE,
+            len: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Number of `get`/`get_mut` calls that found their key.
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of `get`/`get_mut` calls that missed.
+    pub const fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// The entry `pop_lru` would remove.
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        if self.tail == NONE {
+            return None;
+        }
+        let (key, value) = self.entry_at(self.tail);
+        Some((key, value))
+    }
+
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        if self.tail == NONE {
+            None
+        } else {
+            Some(self.remove_at(self.tail))
+        }
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_lru().is_some() {}
+    }
+
+    /// Entries from most to least recently used.
+    pub fn iter(&self) -> Iter<'_, K, V, N> {
+        Iter {
+            lru: self,
+            index: self.head,
+            remaining: self.len,
+        }
+    }
+
+    fn entry_at(&self, index: usize) -> &(K, V) {
+        debug_assert!(index < self.len);
+        // SAFETY: Slots in `[0, len)` are initialized
+        unsafe { self.entries[index].assume_init_ref() }
+    }
+
+    fn entry_at_mut(&mut self, index: usize) -> &mut (K, V) {
+        debug_assert!(index < self.len);
+        // SAFETY: Slots in `[0, len)` are initialized
+        unsafe { self.entries[index].assume_init_mut() }
+    }
+
+    fn detach(&mut self, index: usize) {
+        let (prev, next) = (self.prev[index], self.next[index]);
+        if prev == NONE {
+            self.head = next;
+        } else {
+            self.next[prev] = next;
+        }
+        if next == NONE {
+            self.tail = prev;
+        } else {
+            self.prev[next] = prev;
+        }
+    }
+
+    fn attach_front(&mut self, index: usize) {
+        self.prev[index] = NONE;
+        self.next[index] = self.head;
+        if self.head == NONE {
+            self.tail = index;
+        } else {
+            self.prev[self.head] = index;
+        }
+        self.head = index;
+    }
+
+    fn promote(&mut self, index: usize) {
+        if self.head != index {
+            self.detach(index);
+            self.attach_front(index);
+        }
+    }
+
+    /// Unlinks and returns the entry at `index`, then moves the last slot into
+    /// the gap so the occupied slots stay dense.
+    fn remove_at(&mut self, index: usize) -> (K, V) {
+        self.detach(index);
+        self.len -= 1;
+        // SAFETY: `index` was occupied and is outside the live range or refilled below
+        let removed = unsafe { self.entries[index].assume_init_read() };
+        let last = self.len;
+        if index != last {
+            // SAFETY: The old last slot is initialized and is now outside the live range
+            let moved = unsafe { self.entries[last].assume_init_read() };
+            self.entries[index].write(moved);
+            let (prev, next) = (self.prev[last], self.next[last]);
+            self.prev[index] = prev;
+            self.next[index] = next;
+            if prev == NONE {
+                self.head = index;
+            } else {
+                self.next[prev] = index;
+            }
+            if next == NONE {
+                self.tail = index;
+            } else {
+                self.prev[next] = index;
+            }
+        }
+        removed
+    }
+}
+
+impl<K: Eq, V, const N: usize> StackLru<K, V, N> {
+    /// Looks up `key` and marks it most recently used, updating the hit/miss counters.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        match self.position(key) {
+            Some(index) => {
+                self.hits += 1;
+                self.promote(index);
+                Some(&mut self.entry_at_mut(index).1)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Looks up `key` without touching the recency order or the counters.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).map(|index| &self.entry_at(index).1)
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        self.position(key).is_some()
+    }
+
+    /// Inserts `key` as the most recently used entry and returns whatever left the cache.
+    ///
+    /// A zero-capacity cache evicts the new entry straight away.
+    pub fn put(&mut self, key: K, value: V) -> Option<Displaced<K, V>> {
+        if let Some(index) = self.position(&key) {
+            self.promote(index);
+            let old = mem::replace(&mut self.entry_at_mut(index).1, value);
+            return Some(Displaced::Replaced(old));
+        }
+        if N == 0 {
+            return Some(Displaced::Evicted(key, value));
+        }
+        let evicted = if self.is_full() { self.pop_lru() } else { None };
+        let index = self.len;
+        self.entries[index].write((key, value));
+        self.len += 1;
+        self.attach_front(index);
+        evicted.map(|(key, value)| Displaced::Evicted(key, value))
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.position(key)?;
+        Some(self.remove_at(index).1)
+    }
+
+    fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        (0..self.len).find(|&index| self.entry_at(index).0.borrow() == key)
+    }
+}
+
+impl<K, V, const N: usize> Default for StackLru<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, const N: usize> Drop for StackLru<K, V, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K: Clone, V: Clone, const N: usize> Clone for StackLru<K, V, N> {
+    fn clone(&self) -> Self {
+        let mut lru = Self::new();
+        // Insert from least to most recently used so the order carries over
+        let mut index = self.tail;
+        while index != NONE {
+            let (key, value) = self.entry_at(index);
+            let slot = lru.len;
+            lru.entries[slot].write((key.clone(), value.clone()));
+            lru.len += 1;
+            lru.attach_front(slot);
+            index = self.prev[index];
+        }
+        lru.hits = self.hits;
+        lru.misses = self.misses;
+        lru
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, const N: usize> fmt::Debug for StackLru<K, V, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over a `StackLru` from most to least recently used.
+pub struct Iter<'a, K, V, const N: usize> {
+    lru: &'a StackLru<K, V, N>,
+    index: usize,
+    remaining: usize,
+}
+
+impl<'a, K, V, const N: usize> Iterator for Iter<'a, K, V, N> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == NONE {
+            return None;
+        }
+        let (key, value) = self.lru.entry_at(self.index);
+        self.index = self.lru.next[self.index];
+        self.remaining -= 1;
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for Iter<'_, K, V, N> {}
+
+impl<K, V, const N: usize> FusedIterator for Iter<'_, K, V, N> {}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a StackLru<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
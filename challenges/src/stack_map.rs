@@ -0,0 +1,687 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FusedIterator;
+use std::mem::{self, MaybeUninit};
+use std::ops::Index;
+use std::slice;
+
+use crate::stack_vector::CapacityError;
+
+/// Fixed-capacity hash map using open addressing with linear probing.
+///
+/// Removal uses backward-shift deletion instead of tombstones, so every probe
+/// sequence ends at the first empty slot and lookups never degrade over time.
+pub struct StackMap<K, V, const N: usize, S = RandomState> {
+    slots: [MaybeUninit<(K, V)>; N],
+    occupied: [bool; N],
+    len: usize,
+    hash_builder: S,
+}
+
+enum Probe {
+    Found(usize),
+    Vacant(usize),
+    Full,
+}
+
+impl<K, V, const N: usize> StackMap<K, V, N> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K, V, const N: usize, S> StackMap<K, V, N, S> {
+    pub const fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            slots: [const { MaybeUninit::uninit() }; N],
+            occupied: [false; N],
+            len: 0,
+            hash_builder,
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Entries in slot order, which is unspecified.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots.iter(),
+            occupied: self.occupied.iter(),
+            remaining: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: self.slots.iter_mut(),
+            occupied: self.occupied.iter(),
+            remaining: self.len,
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut {
+            inner: self.iter_mut(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for index in 0..N {
+            if self.occupied[index] {
+                self.occupied[index] = false;
+                self.len -= 1;
+                // SAFETY: The slot was occupied and is marked empty before dropping
+                unsafe { self.slots[index].assume_init_drop() };
+            }
+        }
+    }
+
+    fn entry_at(&self, index: usize) -> &(K, V) {
+        debug_assert!(self.occupied[index]);
+        // SAFETY: Callers only pass indices of occupied slots
+        unsafe { self.slots[index].assume_init_ref() }
+    }
+
+    fn entry_at_mut(&mut self, index: usize) -> &mut (K, V) {
+        debug_assert!(self.occupied[index]);
+        // SAFETY: Callers only pass indices of occupied slots
+        unsafe { self.slots[index].assume_init_mut() }
+    }
+
+    fn take_at(&mut self, index: usize) -> (K, V) {
+        debug_assert!(self.occupied[index]);
+        self.occupied[index] = false;
+        self.len -= 1;
+        // SAFETY: The slot was occupied and is now marked empty, so it is read only once
+        unsafe { self.slots[index].assume_init_read() }
+    }
+
+    fn write_at(&mut self, index: usize, key: K, value: V) -> &mut (K, V) {
+        debug_assert!(!self.occupied[index]);
+        self.occupied[index] = true;
+        self.len += 1;
+        self.slots[index].write((key, value))
+    }
+
+    #[inline(always)]
+    fn next_index(index: usize) -> usize {
+        if index + 1 == N {
+            0
+        } else {
+            index + 1
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize, S: BuildHasher> StackMap<K, V, N, S> {
+    /// Inserts `key` and returns the previous value, or hands the pair back if the map is full.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>> {
+        match self.probe(&key) {
+            Probe::Found(index) => Ok(Some(mem::replace(&mut self.entry_at_mut(index).1, value))),
+            Probe::Vacant(index) => {
+                self.write_at(index, key, value);
+                Ok(None)
+            }
+            Probe::Full => Err(CapacityError::new((key, value))),
+        }
+    }
+
+    /// Gets the entry for `key`, failing only if the key is absent and the map is full.
+    pub fn entry(&mut self, key: K) -> Result<Entry<'_, K, V, N, S>, CapacityError<K>> {
+        match self.probe(&key) {
+            Probe::Found(index) => Ok(Entry::Occupied(OccupiedEntry { map: self, index })),
+            Probe::Vacant(index) => Ok(Entry::Vacant(VacantEntry {
+                map: self,
+                key,
+                index,
+            })),
+            Probe::Full => Err(CapacityError::new(key)),
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.probe(key) {
+            Probe::Found(index) => {
+                let (key, value) = self.entry_at(index);
+                Some((key, value))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.probe(key) {
+            Probe::Found(index) => Some(&mut self.entry_at_mut(index).1),
+            _ => None,
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        matches!(self.probe(key), Probe::Found(_))
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.probe(key) {
+            Probe::Found(index) => Some(self.remove_at(index, |_, _| {})),
+            _ => None,
+        }
+    }
+
+    /// Keeps only the entries for which `f` returns `true`, visiting each entry once.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        // A backward shift can pull an already visited entry into the slot being
+        // examined, so the visited flags travel with the entries
+        let mut visited = [false; N];
+        for index in 0..N {
+            while self.occupied[index] && !visited[index] {
+                visited[index] = true;
+                let (key, value) = self.entry_at_mut(index);
+                if !f(key, value) {
+                    self.remove_at(index, |from, to| visited[to] = visited[from]);
+                }
+            }
+        }
+    }
+
+    fn ideal_index<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+        (self.hash_builder.hash_one(key) % N as u64) as usize
+    }
+
+    fn probe<Q>(&self, key: &Q) -> Probe
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if N == 0 {
+            return Probe::Full;
+        }
+        let mut index = self.ideal_index(key);
+        for _ in 0..N {
+            if !self.occupied[index] {
+                return Probe::Vacant(index);
+            }
+            if self.entry_at(index).0.borrow() == key {
+                return Probe::Found(index);
+            }
+            index = Self::next_index(index);
+        }
+        Probe::Full
+    }
+
+    /// Removes the entry at `hole` and shifts the rest of its cluster back so no
+    /// probe sequence is broken. `on_move(from, to)` is called for every shifted entry.
+    fn remove_at(&mut self, mut hole: usize, mut on_move: impl FnMut(usize, usize)) -> (K, V) {
+        let removed = self.take_at(hole);
+        let mut index = Self::next_index(hole);
+        while self.occupied[index] {
+            let ideal = self.ideal_index(&self.entry_at(index).0);
+            // The entry may fill the hole only if the hole lies on its probe path
+            let probe_distance = (index + N - ideal) % N;
+            let hole_distance = (index + N - hole) % N;
+            if probe_distance >= hole_distance {
+                let (key, value) = self.take_at(index);
+                self.write_at(hole, key, value);
+                on_move(index, hole);
+                hole = index;
+            }
+            index = Self::next_index(index);
+        }
+        removed
+    }
+}
+
+impl<K, V, const N: usize, S> Drop for StackMap<K, V, N, S> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V, const N: usize, S: Default> Default for StackMap<K, V, N, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: Clone, V: Clone, const N: usize, S: Clone> Clone for StackMap<K, V, N, S> {
+    fn clone(&self) -> Self {
+        let mut map = Self::with_hasher(self.hash_builder.clone());
+        // Same hasher, so every entry can keep its slot
+        for index in 0..N {
+            if self.occupied[index] {
+                let (key, value) = self.entry_at(index);
+                map.write_at(index, key.clone(), value.clone());
+            }
+        }
+        map
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, const N: usize, S> fmt::Debug for StackMap<K, V, N, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, const N: usize, S> PartialEq for StackMap<K, V, N, S>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, const N: usize, S: BuildHasher> Eq for StackMap<K, V, N, S> {}
+
+impl<K, Q, V, const N: usize, S> Index<&Q> for StackMap<K, V, N, S>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    /// Panics if `key` is not present.
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found")
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize, S: BuildHasher> Extend<(K, V)> for StackMap<K, V, N, S> {
+    /// Panics if a new key does not fit.
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            if self.insert(key, value).is_err() {
+                panic!("Capacity exceeded");
+            }
+        }
+    }
+}
+
+impl<K, V, const N: usize, S> FromIterator<(K, V)> for StackMap<K, V, N, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize, const M: usize> From<[(K, V); M]> for StackMap<K, V, N> {
+    /// Panics if the array holds more than `N` distinct keys.
+    fn from(entries: [(K, V); M]) -> Self {
+        let mut map = Self::new();
+        map.extend(entries);
+        map
+    }
+}
+
+/// View into a single slot of a `StackMap`.
+pub enum Entry<'a, K, V, const N: usize, S> {
+    Occupied(OccupiedEntry<'a, K, V, N, S>),
+    Vacant(VacantEntry<'a, K, V, N, S>),
+}
+
+pub struct OccupiedEntry<'a, K, V, const N: usize, S> {
+    map: &'a mut StackMap<K, V, N, S>,
+    index: usize,
+}
+
+pub struct VacantEntry<'a, K, V, const N: usize, S> {
+    map: &'a mut StackMap<K, V, N, S>,
+    key: K,
+    index: usize,
+}
+
+impl<'a, K, V, const N: usize, S> Entry<'a, K, V, N, S> {
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V, const N: usize, S> OccupiedEntry<'a, K, V, N, S> {
+    pub fn key(&self) -> &K {
+        &self.map.entry_at(self.index).0
+    }
+
+    pub fn get(&self) -> &V {
+        &self.map.entry_at(self.index).1
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.entry_at_mut(self.index).1
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.entry_at_mut(self.index).1
+    }
+
+    /// Replaces the value, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize, S: BuildHasher> OccupiedEntry<'_, K, V, N, S> {
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_at(self.index, |_, _| {})
+    }
+}
+
+impl<'a, K, V, const N: usize, S> VacantEntry<'a, K, V, N, S> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        &mut self.map.write_at(self.index, self.key, value).1
+    }
+}
+
+/// Iterator over the entries of a `StackMap`.
+pub struct Iter<'a, K, V> {
+    slots: slice::Iter<'a, MaybeUninit<(K, V)>>,
+    occupied: slice::Iter<'a, bool>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let slot = self.slots.next()?;
+            if *self.occupied.next()? {
+                self.remaining -= 1;
+                // SAFETY: The slot is occupied and the map is borrowed for `'a`
+                let (key, value) = unsafe { slot.assume_init_ref() };
+                return Some((key, value));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            occupied: self.occupied.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+/// Iterator over the entries of a `StackMap` with mutable values.
+pub struct IterMut<'a, K, V> {
+    slots: slice::IterMut<'a, MaybeUninit<(K, V)>>,
+    occupied: slice::Iter<'a, bool>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let slot = self.slots.next()?;
+            if *self.occupied.next()? {
+                self.remaining -= 1;
+                // SAFETY: The slot is occupied and the map is mutably borrowed for `'a`
+                let (key, value) = unsafe { slot.assume_init_mut() };
+                return Some((&*key, value));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+
+pub struct Keys<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+impl<K, V> Clone for Keys<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+pub struct Values<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+pub struct ValuesMut<'a, K, V> {
+    inner: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, value)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for ValuesMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for ValuesMut<'_, K, V> {}
+
+/// By-value iterator over a `StackMap`.
+pub struct IntoIter<K, V, const N: usize, S> {
+    map: StackMap<K, V, N, S>,
+    index: usize,
+}
+
+impl<K, V, const N: usize, S> Iterator for IntoIter<K, V, N, S> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < N {
+            let index = self.index;
+            self.index += 1;
+            if self.map.occupied[index] {
+                // Probe sequences no longer matter, the map is only drained from here
+                return Some(self.map.take_at(index));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.map.len, Some(self.map.len))
+    }
+}
+
+impl<K, V, const N: usize, S> ExactSizeIterator for IntoIter<K, V, N, S> {}
+
+impl<K, V, const N: usize, S> FusedIterator for IntoIter<K, V, N, S> {}
+
+impl<K, V, const N: usize, S> IntoIterator for StackMap<K, V, N, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, N, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            map: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize, S> IntoIterator for &'a StackMap<K, V, N, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize, S> IntoIterator for &'a mut StackMap<K, V, N, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -1,6 +1,44 @@
+use std::cmp::Ordering;
+use std::error::Error;
 use std::fmt;
-use std::iter::{Extend, FromIterator};
-use std::mem::MaybeUninit;
+use std::hash::{Hash, Hasher};
+use std::iter::{Extend, FromIterator, FusedIterator};
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
+use std::ptr;
+use std::slice;
+
+/// Error returned when pushing into a full `StackQueue`.
+///
+/// Carries the rejected item back to the caller so nothing is lost.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct QueueFullError<T> {
+    item: T,
+}
+
+impl<T> QueueFullError<T> {
+    pub const fn new(item: T) -> Self {
+        Self { item }
+    }
+
+    pub fn item(self) -> T {
+        self.item
+    }
+}
+
+impl<T> fmt::Debug for QueueFullError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "QueueFullError: queue is full")
+    }
+}
+
+impl<T> fmt::Display for QueueFullError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Queue is full")
+    }
+}
+
+impl<T> Error for QueueFullError<T> {}
 
 pub struct StackQueue<T, const N: usize> {
     data: [MaybeUninit<T>; N],
@@ -10,18 +48,30 @@ pub struct StackQueue<T, const N: usize> {
 }
 
 impl<T, const N: usize> StackQueue<T, N> {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
-            data: unsafe { MaybeUninit::uninit().assume_init() },
+            data: [const { MaybeUninit::uninit() }; N],
             front: 0,
             rear: 0,
             size: 0,
         }
     }
 
-    pub fn push(&mut self, item: T) -> Result<(), String> {
+    /// Creates a full queue from an array, the first element being the front.
+    pub const fn from_array(array: [T; N]) -> Self {
+        let array = ManuallyDrop::new(array);
+        Self {
+            // SAFETY: `ManuallyDrop<[T; N]>` has the same layout as `[MaybeUninit<T>; N]`
+            data: unsafe { ptr::read(&array as *const _ as *const [MaybeUninit<T>; N]) },
+            front: 0,
+            rear: 0,
+            size: N,
+        }
+    }
+
+    pub fn push(&mut self, item: T) -> Result<(), QueueFullError<T>> {
         if self.size >= N {
-            Err("Queue is full".to_string())
+            Err(QueueFullError::new(item))
         } else {
             // SAFETY: The container is guaranteed to have space
             unsafe {
@@ -45,6 +95,183 @@ impl<T, const N: usize> StackQueue<T, N> {
         }
     }
 
+    /// Pushes every item from `iter`, returning how many were dropped because the queue was full.
+    ///
+    /// The iterator is always consumed to the end.
+    pub fn extend_counted<I: IntoIterator<Item = T>>(&mut self, iter: I) -> usize {
+        let mut dropped = 0;
+        for item in iter {
+            if self.push(item).is_err() {
+                dropped += 1;
+            }
+        }
+        dropped
+    }
+
+    /// Collects `iter` into a new queue, also returning how many items did not fit.
+    pub fn from_iter_counted<I: IntoIterator<Item = T>>(iter: I) -> (Self, usize) {
+        let mut queue = Self::new();
+        let dropped = queue.extend_counted(iter);
+        (queue, dropped)
+    }
+
+    /// Pushes `item` to the back, evicting and returning the oldest element if the queue is full.
+    pub fn push_overwrite(&mut self, item: T) -> Option<T> {
+        if N == 0 {
+            return Some(item);
+        }
+        if self.size < N {
+            // SAFETY: The container is guaranteed to have space
+            unsafe {
+                self.data[self.rear].as_mut_ptr().write(item);
+            }
+            self.rear = (self.rear + 1) % N;
+            self.size += 1;
+            None
+        } else {
+            // SAFETY: When full, `front == rear` and the slot holds the oldest element
+            let evicted = unsafe { self.data[self.front].as_ptr().read() };
+            unsafe {
+                self.data[self.rear].as_mut_ptr().write(item);
+            }
+            self.front = (self.front + 1) % N;
+            self.rear = self.front;
+            Some(evicted)
+        }
+    }
+
+    /// Rearranges the storage so the elements are contiguous and returns them in FIFO order.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.front + self.size > N {
+            // Rotating the raw slots keeps every element's bits intact, initialized or not
+            self.data.rotate_left(self.front);
+            self.front = 0;
+            self.rear = self.size % N;
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Alias of `push`, for symmetry with `push_front`.
+    pub fn push_back(&mut self, item: T) -> Result<(), QueueFullError<T>> {
+        self.push(item)
+    }
+
+    /// Alias of `pop`, for symmetry with `pop_back`.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    pub fn push_front(&mut self, item: T) -> Result<(), QueueFullError<T>> {
+        if self.size >= N {
+            Err(QueueFullError::new(item))
+        } else {
+            self.front = if self.front == 0 {
+                N - 1
+            } else {
+                self.front - 1
+            };
+            // SAFETY: The slot before the front is free when the container is not full
+            unsafe {
+                self.data[self.front].as_mut_ptr().write(item);
+            }
+            self.size += 1;
+            Ok(())
+        }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.size == 0 {
+            None
+        } else {
+            self.rear = if self.rear == 0 { N - 1 } else { self.rear - 1 };
+            self.size -= 1;
+            // SAFETY: The slot held the last live element
+            Some(unsafe { self.data[self.rear].as_mut_ptr().read() })
+        }
+    }
+
+    /// Returns the element `index` positions from the front.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.size {
+            // SAFETY: Logical indices below `size` map to initialized slots
+            unsafe { Some(&*self.data[self.physical_index(index)].as_ptr()) }
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.size {
+            let index = self.physical_index(index);
+            // SAFETY: Logical indices below `size` map to initialized slots
+            unsafe { Some(&mut *self.data[index].as_mut_ptr()) }
+        } else {
+            None
+        }
+    }
+
+    /// Rotates the queue `n` places to the left, so the element at `n` becomes the front.
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.size, "Rotation exceeds length");
+        if n <= self.size - n {
+            for _ in 0..n {
+                self.move_front_to_back();
+            }
+        } else {
+            for _ in 0..self.size - n {
+                self.move_back_to_front();
+            }
+        }
+    }
+
+    /// Rotates the queue `n` places to the right, so the last `n` elements move to the front.
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.size, "Rotation exceeds length");
+        self.rotate_left(self.size - n);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (head, tail) = self.as_slices();
+        Iter {
+            head: head.iter(),
+            tail: tail.iter(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (head, tail) = self.as_mut_slices();
+        IterMut {
+            head: head.iter_mut(),
+            tail: tail.iter_mut(),
+        }
+    }
+
+    /// Returns the contents in FIFO order as two slices, the second one being
+    /// the part that wrapped around to the start of the storage.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (head, tail) = self.slice_ranges();
+        let base = self.data.as_ptr() as *const T;
+        // SAFETY: Both ranges cover initialized, non-overlapping slots
+        unsafe {
+            (
+                slice::from_raw_parts(base.add(head.0), head.1),
+                slice::from_raw_parts(base.add(tail.0), tail.1),
+            )
+        }
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (head, tail) = self.slice_ranges();
+        let base = self.data.as_mut_ptr() as *mut T;
+        // SAFETY: Both ranges cover initialized, non-overlapping slots
+        unsafe {
+            (
+                slice::from_raw_parts_mut(base.add(head.0), head.1),
+                slice::from_raw_parts_mut(base.add(tail.0), tail.1),
+            )
+        }
+    }
+
     pub fn front(&self) -> Option<&T> {
         if self.size == 0 {
             None
@@ -84,66 +311,477 @@ impl<T, const N: usize> StackQueue<T, N> {
     }
 
     #[inline(always)]
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.size == 0
     }
 
     #[inline(always)]
-    pub fn is_full(&self) -> bool {
+    pub const fn is_full(&self) -> bool {
         self.size == N
     }
 
     #[inline(always)]
-    pub fn len(&self) -> usize {
+    pub const fn len(&self) -> usize {
         self.size
     }
 
+    /// Drops every element and resets the queue.
     pub fn clear(&mut self) {
+        self.truncate_back(0);
         self.front = 0;
         self.rear = 0;
-        self.size = 0;
+    }
+
+    /// Shortens the queue to `len` elements, dropping elements from the front.
+    pub fn truncate_front(&mut self, len: usize) {
+        while self.size > len {
+            self.pop();
+        }
+    }
+
+    /// Shortens the queue to `len` elements, dropping elements from the back.
+    pub fn truncate_back(&mut self, len: usize) {
+        while self.size > len {
+            self.pop_back();
+        }
+    }
+
+    /// Removes the elements in the logical `range` and yields them in FIFO order.
+    ///
+    /// Elements not consumed by the iterator are dropped when it goes out of scope.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, N>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.size;
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(start <= end, "Drain start is greater than end");
+        assert!(end <= len, "Index out of bounds");
+
+        // Only the head stays visible while the drain is alive, the rest leaks if it is forgotten
+        self.size = start;
+        if N > 0 {
+            self.rear = (self.front + start) % N;
+        }
+        Drain {
+            queue: self,
+            start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+        }
+    }
+
+    #[inline(always)]
+    fn physical_index(&self, logical: usize) -> usize {
+        (self.front + logical) % N
+    }
+
+    /// `(start, len)` of the two contiguous runs making up the queue.
+    fn slice_ranges(&self) -> ((usize, usize), (usize, usize)) {
+        if self.front + self.size <= N {
+            ((self.front, self.size), (0, 0))
+        } else {
+            let head_len = N - self.front;
+            ((self.front, head_len), (0, self.size - head_len))
+        }
+    }
+
+    fn move_front_to_back(&mut self) {
+        // SAFETY: The front slot is live, and the rear slot is either free or the same slot
+        unsafe {
+            let item = self.data[self.front].as_ptr().read();
+            self.data[self.rear].as_mut_ptr().write(item);
+        }
+        self.front = (self.front + 1) % N;
+        self.rear = (self.rear + 1) % N;
+    }
+
+    fn move_back_to_front(&mut self) {
+        self.rear = if self.rear == 0 { N - 1 } else { self.rear - 1 };
+        self.front = if self.front == 0 {
+            N - 1
+        } else {
+            self.front - 1
+        };
+        // SAFETY: The old last slot is live, and the new front slot is either free or the same slot
+        unsafe {
+            let item = self.data[self.rear].as_ptr().read();
+            self.data[self.front].as_mut_ptr().write(item);
+        }
+    }
+}
+
+pub struct Drain<'a, T, const N: usize> {
+    queue: &'a mut StackQueue<T, N>,
+    start: usize,
+    end: usize,
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<T, const N: usize> Iterator for Drain<'_, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            let index = self.queue.physical_index(self.start);
+            self.start += 1;
+            // SAFETY: Elements in `[start, end)` are owned by the drain and not yet yielded
+            Some(unsafe { self.queue.data[index].as_ptr().read() })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for Drain<'_, T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            self.end -= 1;
+            let index = self.queue.physical_index(self.end);
+            // SAFETY: Elements in `[start, end)` are owned by the drain and not yet yielded
+            Some(unsafe { self.queue.data[index].as_ptr().read() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Drain<'_, T, N> {}
+
+impl<T, const N: usize> FusedIterator for Drain<'_, T, N> {}
+
+impl<T, const N: usize> Drop for Drain<'_, T, N> {
+    fn drop(&mut self) {
+        // Moves the kept tail up to the head even if an element destructor panics
+        struct MoveTail<'r, 'a, T, const N: usize>(&'r mut Drain<'a, T, N>);
+
+        impl<T, const N: usize> Drop for MoveTail<'_, '_, T, N> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let queue = &mut *drain.queue;
+                let head_len = queue.size;
+                if head_len == 0 && drain.tail_len > 0 {
+                    // Draining from the front only needs the front index moved
+                    queue.front = queue.physical_index(drain.tail_start);
+                    queue.size = drain.tail_len;
+                    queue.rear = queue.physical_index(queue.size);
+                    return;
+                }
+                for i in 0..drain.tail_len {
+                    let src = queue.physical_index(drain.tail_start + i);
+                    let dst = queue.physical_index(head_len + i);
+                    if src != dst {
+                        // SAFETY: `dst` is a vacated slot, `src` is a live tail element
+                        unsafe {
+                            let value = queue.data[src].as_ptr().read();
+                            queue.data[dst].as_mut_ptr().write(value);
+                        }
+                    }
+                }
+                queue.size = head_len + drain.tail_len;
+                if N > 0 {
+                    queue.rear = queue.physical_index(queue.size);
+                }
+            }
+        }
+
+        let guard = MoveTail(self);
+        while guard.0.start < guard.0.end {
+            let index = guard.0.queue.physical_index(guard.0.start);
+            guard.0.start += 1;
+            // SAFETY: The element was never yielded
+            unsafe { ptr::drop_in_place(guard.0.queue.data[index].as_mut_ptr()) };
+        }
+    }
+}
+
+impl<T, const N: usize> Index<usize> for StackQueue<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("Index out of bounds")
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for StackQueue<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("Index out of bounds")
+    }
+}
+
+impl<T, const N: usize> Default for StackQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl<T, const N: usize> Drop for StackQueue<T, N> {
     fn drop(&mut self) {
-        while let Some(_) = self.pop() {}
+        self.clear();
     }
 }
 
 impl<T: fmt::Debug, const N: usize> fmt::Debug for StackQueue<T, N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut elements = Vec::with_capacity(self.size);
-        for i in 0..self.size {
-            let index = (self.front + i) % N;
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for StackQueue<T, N> {
+    fn clone(&self) -> Self {
+        let mut queue = Self::new();
+        for item in self.iter() {
+            // SAFETY: The clone has the same capacity as the original
             unsafe {
-                elements.push(&*self.data[index].as_ptr());
+                queue.data[queue.rear].as_mut_ptr().write(item.clone());
             }
+            queue.rear = (queue.rear + 1) % N;
+            queue.size += 1;
         }
-        f.debug_list().entries(elements).finish()
+        queue
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for StackQueue<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for StackQueue<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for StackQueue<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for StackQueue<T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for StackQueue<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hashes the logical contents, so equal queues hash equally wherever they wrap
+        state.write_usize(self.size);
+        self.iter().for_each(|item| item.hash(state));
+    }
+}
+
+/// Borrowing iterator over a `StackQueue` in FIFO order.
+pub struct Iter<'a, T> {
+    head: slice::Iter<'a, T>,
+    tail: slice::Iter<'a, T>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+            tail: self.tail.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.head.next().or_else(|| self.tail.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.head.len() + self.tail.len();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.tail.next_back().or_else(|| self.head.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Mutably borrowing iterator over a `StackQueue` in FIFO order.
+pub struct IterMut<'a, T> {
+    head: slice::IterMut<'a, T>,
+    tail: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.head.next().or_else(|| self.tail.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.head.len() + self.tail.len();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.tail.next_back().or_else(|| self.head.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// By-value iterator over a `StackQueue` in FIFO order.
+///
+/// Owns the queue, so items that are never yielded are dropped with it.
+pub struct IntoIter<T, const N: usize> {
+    queue: StackQueue<T, N>,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    /// Returns the items that have not been yielded yet.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.queue.as_slices()
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.queue.pop_back()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T: Clone, const N: usize> Clone for IntoIter<T, N> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.queue).finish()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for StackQueue<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { queue: self }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a StackQueue<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut StackQueue<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
     }
 }
 
 impl<T, const N: usize> FromIterator<T> for StackQueue<T, N> {
+    /// Items that do not fit are dropped, use `from_iter_counted` to find out how many.
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut queue = Self::new();
-        for item in iter {
-            if queue.push(item).is_err() {
-                // Stop if the queue is full
-                break;
-            }
-        }
-        queue
+        Self::from_iter_counted(iter).0
     }
 }
 
 impl<T, const N: usize> Extend<T> for StackQueue<T, N> {
+    /// Items that do not fit are dropped, use `extend_counted` to find out how many.
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for item in iter {
-            if self.push(item).is_err() {
-                // Stop if the queue is full
-                break;
+        self.extend_counted(iter);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::StackQueue;
+    use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    /// Serialized as a sequence in FIFO order, front first.
+    impl<T: Serialize, const N: usize> Serialize for StackQueue<T, N> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for StackQueue<T, N> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct StackQueueVisitor<T, const N: usize>(PhantomData<T>);
+
+            impl<'de, T: Deserialize<'de>, const N: usize> Visitor<'de> for StackQueueVisitor<T, N> {
+                type Value = StackQueue<T, N>;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a sequence of at most {} elements", N)
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut queue = StackQueue::new();
+                    while let Some(item) = seq.next_element()? {
+                        if queue.push(item).is_err() {
+                            return Err(A::Error::invalid_length(N + 1, &self));
+                        }
+                    }
+                    Ok(queue)
+                }
             }
+
+            deserializer.deserialize_seq(StackQueueVisitor::<T, N>(PhantomData))
         }
     }
 }
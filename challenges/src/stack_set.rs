@@ -0,0 +1,215 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FusedIterator;
+
+use crate::stack_map::{self, Keys, StackMap};
+use crate::stack_vector::CapacityError;
+
+/// Fixed-capacity hash set, a `StackMap` with unit values.
+pub struct StackSet<K, const N: usize, S = RandomState> {
+    map: StackMap<K, (), N, S>,
+}
+
+/// Iterator over the elements of a `StackSet`.
+pub type Iter<'a, K> = Keys<'a, K, ()>;
+
+impl<K, const N: usize> StackSet<K, N> {
+    pub fn new() -> Self {
+        Self {
+            map: StackMap::new(),
+        }
+    }
+}
+
+impl<K, const N: usize, S> StackSet<K, N, S> {
+    pub const fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            map: StackMap::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.map.hasher()
+    }
+
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.map.is_full()
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn iter(&self) -> Iter<'_, K> {
+        self.map.keys()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+}
+
+impl<K: Hash + Eq, const N: usize, S: BuildHasher> StackSet<K, N, S> {
+    /// Returns whether `key` was newly added, or hands it back if the set is full.
+    pub fn insert(&mut self, key: K) -> Result<bool, CapacityError<K>> {
+        self.map
+            .insert(key, ())
+            .map(|previous| previous.is_none())
+            .map_err(|e| CapacityError::new(e.element().0))
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get_key_value(key).map(|(key, _)| key)
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(key).is_some()
+    }
+
+    pub fn take<Q>(&mut self, key: &Q) -> Option<K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove_entry(key).map(|(key, _)| key)
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.map.retain(|key, _| f(key));
+    }
+
+    pub fn is_subset<const M: usize, T: BuildHasher>(&self, other: &StackSet<K, M, T>) -> bool {
+        self.len() <= other.len() && self.iter().all(|key| other.contains(key))
+    }
+
+    pub fn is_disjoint<const M: usize, T: BuildHasher>(&self, other: &StackSet<K, M, T>) -> bool {
+        self.iter().all(|key| !other.contains(key))
+    }
+}
+
+impl<K, const N: usize, S: Default> Default for StackSet<K, N, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: Clone, const N: usize, S: Clone> Clone for StackSet<K, N, S> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<K: fmt::Debug, const N: usize, S> fmt::Debug for StackSet<K, N, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, const N: usize, S: BuildHasher> PartialEq for StackSet<K, N, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<K: Hash + Eq, const N: usize, S: BuildHasher> Eq for StackSet<K, N, S> {}
+
+impl<K: Hash + Eq, const N: usize, S: BuildHasher> Extend<K> for StackSet<K, N, S> {
+    /// Panics if a new element does not fit.
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|key| (key, ())));
+    }
+}
+
+impl<K, const N: usize, S> FromIterator<K> for StackSet<K, N, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut set = Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K: Hash + Eq, const N: usize, const M: usize> From<[K; M]> for StackSet<K, N> {
+    /// Panics if the array holds more than `N` distinct elements.
+    fn from(keys: [K; M]) -> Self {
+        let mut set = Self::new();
+        set.extend(keys);
+        set
+    }
+}
+
+/// By-value iterator over a `StackSet`.
+pub struct IntoIter<K, const N: usize, S> {
+    inner: stack_map::IntoIter<K, (), N, S>,
+}
+
+impl<K, const N: usize, S> Iterator for IntoIter<K, N, S> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, const N: usize, S> ExactSizeIterator for IntoIter<K, N, S> {}
+
+impl<K, const N: usize, S> FusedIterator for IntoIter<K, N, S> {}
+
+impl<K, const N: usize, S> IntoIterator for StackSet<K, N, S> {
+    type Item = K;
+    type IntoIter = IntoIter<K, N, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.map.into_iter(),
+        }
+    }
+}
+
+impl<'a, K, const N: usize, S> IntoIterator for &'a StackSet<K, N, S> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,324 @@
+use std::fmt;
+use std::iter::{Enumerate, FusedIterator};
+use std::mem::MaybeUninit;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+use crate::stack_vector::CapacityError;
+
+/// Sentinel for an empty free list.
+const NONE: usize = usize::MAX;
+
+/// Key returned by `StackSlab::insert`.
+///
+/// A handle stays valid until its entry is removed. Each removal bumps the
+/// slot's generation, so a handle to a reused slot is detected as stale
+/// (until the 32-bit generation wraps around).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+impl Handle {
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    pub const fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// Fixed-capacity object pool that reuses freed slots.
+pub struct StackSlab<T, const N: usize> {
+    slots: [MaybeUninit<T>; N],
+    occupied: [bool; N],
+    generations: [u32; N],
+    next_free: [usize; N],
+    free_head: usize,
+    /// Slots at or past this index have never been handed out.
+    untouched: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> StackSlab<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { MaybeUninit::uninit() }; N],
+            occupied: [false; N],
+            generations: [0; N],
+            next_free: [NONE; N],
+            free_head: NONE,
+            untouched: 0,
+            len: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Stores `value` and returns its handle, or hands `value` back if every slot is taken.
+    pub fn insert(&mut self, value: T) -> Result<Handle, CapacityError<T>> {
+        let index = if self.free_head != NONE {
+            let index = self.free_head;
+            self.free_head = self.next_free[index];
+            index
+        } else if self.untouched < N {
+            self.untouched += 1;
+            self.untouched - 1
+        } else {
+            return Err(CapacityError::new(value));
+        };
+        self.slots[index].write(value);
+        self.occupied[index] = true;
+        self.len += 1;
+        Ok(Handle {
+            index,
+            generation: self.generations[index],
+        })
+    }
+
+    /// Removes the entry, returning `None` if the handle is stale.
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        if !self.contains(handle) {
+            return None;
+        }
+        Some(self.remove_at(handle.index))
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        handle.index < N
+            && self.occupied[handle.index]
+            && self.generations[handle.index] == handle.generation
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        if self.contains(handle) {
+            // SAFETY: `contains` checked that the slot is occupied
+            Some(unsafe { self.slots[handle.index].assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        if self.contains(handle) {
+            // SAFETY: `contains` checked that the slot is occupied
+            Some(unsafe { self.slots[handle.index].assume_init_mut() })
+        } else {
+            None
+        }
+    }
+
+    /// Removes every entry; all outstanding handles become stale.
+    pub fn clear(&mut self) {
+        for index in 0..self.untouched {
+            if self.occupied[index] {
+                drop(self.remove_at(index));
+            }
+        }
+    }
+
+    /// Keeps only the entries for which `f` returns `true`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Handle, &mut T) -> bool,
+    {
+        for index in 0..self.untouched {
+            if self.occupied[index] {
+                let handle = Handle {
+                    index,
+                    generation: self.generations[index],
+                };
+                // SAFETY: The slot is occupied
+                let value = unsafe { self.slots[index].assume_init_mut() };
+                if !f(handle, value) {
+                    drop(self.remove_at(index));
+                }
+            }
+        }
+    }
+
+    /// Live entries in slot order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            slots: self.slots[..self.untouched].iter().enumerate(),
+            occupied: &self.occupied,
+            generations: &self.generations,
+            remaining: self.len,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            slots: self.slots[..self.untouched].iter_mut().enumerate(),
+            occupied: &self.occupied,
+            generations: &self.generations,
+            remaining: self.len,
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> T {
+        debug_assert!(self.occupied[index]);
+        self.occupied[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.next_free[index] = self.free_head;
+        self.free_head = index;
+        self.len -= 1;
+        // SAFETY: The slot was occupied and is now marked free, so it is read only once
+        unsafe { self.slots[index].assume_init_read() }
+    }
+}
+
+impl<T, const N: usize> Default for StackSlab<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for StackSlab<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for StackSlab<T, N> {
+    /// Clones keep slot layout and generations, so handles work on both copies.
+    fn clone(&self) -> Self {
+        let mut slab = Self::new();
+        slab.generations = self.generations;
+        slab.next_free = self.next_free;
+        slab.free_head = self.free_head;
+        slab.untouched = self.untouched;
+        for (handle, value) in self.iter() {
+            slab.slots[handle.index].write(value.clone());
+            slab.occupied[handle.index] = true;
+            slab.len += 1;
+        }
+        slab
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StackSlab<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize> Index<Handle> for StackSlab<T, N> {
+    type Output = T;
+
+    /// Panics if the handle is stale.
+    fn index(&self, handle: Handle) -> &T {
+        self.get(handle).expect("stale handle")
+    }
+}
+
+impl<T, const N: usize> IndexMut<Handle> for StackSlab<T, N> {
+    fn index_mut(&mut self, handle: Handle) -> &mut T {
+        self.get_mut(handle).expect("stale handle")
+    }
+}
+
+/// Iterator over the live entries of a `StackSlab`.
+pub struct Iter<'a, T> {
+    slots: Enumerate<slice::Iter<'a, MaybeUninit<T>>>,
+    occupied: &'a [bool],
+    generations: &'a [u32],
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Handle, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (index, slot) = self.slots.next()?;
+            if self.occupied[index] {
+                self.remaining -= 1;
+                let handle = Handle {
+                    index,
+                    generation: self.generations[index],
+                };
+                // SAFETY: The slot is occupied and the slab is borrowed for `'a`
+                return Some((handle, unsafe { slot.assume_init_ref() }));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Iterator over the live entries of a `StackSlab` with mutable values.
+pub struct IterMut<'a, T> {
+    slots: Enumerate<slice::IterMut<'a, MaybeUninit<T>>>,
+    occupied: &'a [bool],
+    generations: &'a [u32],
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (Handle, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (index, slot) = self.slots.next()?;
+            if self.occupied[index] {
+                self.remaining -= 1;
+                let handle = Handle {
+                    index,
+                    generation: self.generations[index],
+                };
+                // SAFETY: The slot is occupied and the slab is mutably borrowed for `'a`
+                return Some((handle, unsafe { slot.assume_init_mut() }));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+impl<'a, T, const N: usize> IntoIterator for &'a StackSlab<T, N> {
+    type Item = (Handle, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut StackSlab<T, N> {
+    type Item = (Handle, &'a mut T);
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,292 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use std::str::{self, FromStr};
+
+use crate::stack_vector::{CapacityError, StackVector};
+
+/// Fixed-capacity UTF-8 string stored inline in a `StackVector<u8, N>`.
+///
+/// `N` is the capacity in bytes. Every operation keeps the contents valid UTF-8.
+#[derive(Clone, Default)]
+pub struct StackString<const N: usize> {
+    bytes: StackVector<u8, N>,
+}
+
+impl<const N: usize> StackString<N> {
+    pub const fn new() -> Self {
+        Self {
+            bytes: StackVector::new(),
+        }
+    }
+
+    /// Copies as much of `s` as fits, cutting at a char boundary.
+    pub fn from_str_truncating(s: &str) -> Self {
+        let mut string = Self::new();
+        string.push_str_truncating(s);
+        string
+    }
+
+    pub const fn as_str(&self) -> &str {
+        // SAFETY: Only whole UTF-8 sequences are ever written
+        unsafe { str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+
+    pub fn as_mut_str(&mut self) -> &mut str {
+        // SAFETY: Only whole UTF-8 sequences are ever written
+        unsafe { str::from_utf8_unchecked_mut(self.bytes.as_mut_slice()) }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Length in bytes.
+    pub const fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline(always)]
+    pub const fn remaining_capacity(&self) -> usize {
+        self.bytes.remaining_capacity()
+    }
+
+    /// Appends `s`, or leaves the string untouched and hands `s` back if it does not fit.
+    pub fn try_push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+        self.bytes
+            .try_extend_from_slice(s.as_bytes())
+            .map_err(|_| CapacityError::new(s))
+    }
+
+    pub fn push_str(&mut self, s: &str) {
+        if self.try_push_str(s).is_err() {
+            panic!("Capacity exceeded");
+        }
+    }
+
+    pub fn try_push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        let mut buffer = [0; 4];
+        self.try_push_str(c.encode_utf8(&mut buffer))
+            .map_err(|_| CapacityError::new(c))
+    }
+
+    pub fn push(&mut self, c: char) {
+        if self.try_push(c).is_err() {
+            panic!("Capacity exceeded");
+        }
+    }
+
+    /// Appends as much of `s` as fits without splitting a char, returning the number of bytes written.
+    pub fn push_str_truncating(&mut self, s: &str) -> usize {
+        let end = floor_char_boundary(s, self.remaining_capacity());
+        self.push_str(&s[..end]);
+        end
+    }
+
+    pub fn pop(&mut self) -> Option<char> {
+        let c = self.as_str().chars().next_back()?;
+        self.bytes.truncate(self.len() - c.len_utf8());
+        Some(c)
+    }
+
+    /// Shortens the string to `new_len` bytes.
+    ///
+    /// Panics if `new_len` does not lie on a char boundary, like `String::truncate`.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len < self.len() {
+            assert!(
+                self.as_str().is_char_boundary(new_len),
+                "new_len does not lie on a char boundary"
+            );
+            self.bytes.truncate(new_len);
+        }
+    }
+
+    /// Shortens the string to at most `max_len` bytes, backing off to the previous char boundary.
+    pub fn truncate_to_char_boundary(&mut self, max_len: usize) {
+        let end = floor_char_boundary(self.as_str(), max_len);
+        self.bytes.truncate(end);
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+    }
+}
+
+/// Largest index `<= max_len` that lies on a char boundary of `s`.
+fn floor_char_boundary(s: &str, max_len: usize) -> usize {
+    if max_len >= s.len() {
+        return s.len();
+    }
+    let mut end = max_len;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    end
+}
+
+impl<const N: usize> Deref for StackString<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> DerefMut for StackString<N> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for StackString<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for StackString<N> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const N: usize> Borrow<str> for StackString<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Write for StackString<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<const N: usize> fmt::Display for StackString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for StackString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> FromStr for StackString<N> {
+    type Err = CapacityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut string = Self::new();
+        string.try_push_str(s).map_err(CapacityError::simplify)?;
+        Ok(string)
+    }
+}
+
+impl<'a, const N: usize> TryFrom<&'a str> for StackString<N> {
+    type Error = CapacityError<&'a str>;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        let mut string = Self::new();
+        string.try_push_str(s)?;
+        Ok(string)
+    }
+}
+
+impl<const N: usize> From<StackString<N>> for String {
+    fn from(string: StackString<N>) -> Self {
+        string.as_str().to_owned()
+    }
+}
+
+impl<const N: usize> PartialEq for StackString<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<const N: usize> Eq for StackString<N> {}
+
+impl<const N: usize> PartialEq<str> for StackString<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for StackString<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> PartialOrd for StackString<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for StackString<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const N: usize> Hash for StackString<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Must match the `str` hash since the string implements `Borrow<str>`
+        self.as_str().hash(state)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::StackString;
+    use serde::de::{Deserialize, Deserializer, Error, Unexpected, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::fmt;
+
+    impl<const N: usize> Serialize for StackString<N> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+
+    impl<'de, const N: usize> Deserialize<'de> for StackString<N> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct StackStringVisitor<const N: usize>;
+
+            impl<'de, const N: usize> Visitor<'de> for StackStringVisitor<N> {
+                type Value = StackString<N>;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a string of at most {} bytes", N)
+                }
+
+                fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                    StackString::try_from(v).map_err(|_| E::invalid_length(v.len(), &self))
+                }
+
+                fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                    let s = std::str::from_utf8(v)
+                        .map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+                    self.visit_str(s)
+                }
+            }
+
+            deserializer.deserialize_str(StackStringVisitor::<N>)
+        }
+    }
+}
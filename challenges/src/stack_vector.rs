@@ -1,9 +1,71 @@
+use std::borrow::{Borrow, BorrowMut};
+use std::cmp::Ordering;
+use std::error::Error;
 use std::fmt;
-use std::iter::DoubleEndedIterator;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::iter::{DoubleEndedIterator, FusedIterator};
 use std::mem;
-use std::ops::{Index, IndexMut};
+use std::ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds};
 use std::ptr;
-use std::slice;
+use std::slice::{self, SliceIndex};
+
+/// Creates a `StackVector` whose capacity is the number of elements given.
+///
+/// Expands to a `const` expression, so it can initialize `const` and `static` items:
+///
+/// ```
+/// use challenges::stack_vec;
+/// use challenges::stack_vector::StackVector;
+///
+/// static PRIMES: StackVector<u32, 4> = stack_vec![2, 3, 5, 7];
+/// assert_eq!(PRIMES[2], 5);
+/// ```
+#[macro_export]
+macro_rules! stack_vec {
+    () => {
+        $crate::stack_vector::StackVector::new()
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::stack_vector::StackVector::from_array([$($x),+])
+    };
+}
+
+/// Error returned when an operation would grow a `StackVector` past `MAX_SIZE`.
+///
+/// Carries the rejected value back to the caller so nothing is lost.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+
+    pub fn element(self) -> T {
+        self.element
+    }
+
+    pub fn simplify(self) -> CapacityError {
+        CapacityError { element: () }
+    }
+}
+
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CapacityError: capacity exceeded")
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "capacity exceeded")
+    }
+}
+
+impl<T> Error for CapacityError<T> {}
 
 pub struct StackVector<T, const MAX_SIZE: usize> {
     data: [mem::MaybeUninit<T>; MAX_SIZE],
@@ -11,29 +73,102 @@ pub struct StackVector<T, const MAX_SIZE: usize> {
 }
 
 impl<T, const MAX_SIZE: usize> StackVector<T, MAX_SIZE> {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
-            data: unsafe { mem::MaybeUninit::uninit().assume_init() },
+            data: [const { mem::MaybeUninit::uninit() }; MAX_SIZE],
             size: 0,
         }
     }
 
-    pub fn len(&self) -> usize {
+    /// Creates a full vector from an array, usable in `const` and `static` items.
+    pub const fn from_array(array: [T; MAX_SIZE]) -> Self {
+        let array = mem::ManuallyDrop::new(array);
+        Self {
+            // SAFETY: `ManuallyDrop<[T; N]>` has the same layout as `[MaybeUninit<T>; N]`
+            data: unsafe {
+                ptr::read(&array as *const _ as *const [mem::MaybeUninit<T>; MAX_SIZE])
+            },
+            size: MAX_SIZE,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
         self.size
     }
 
-    pub fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         self.size == 0
     }
 
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        MAX_SIZE
+    }
+
+    #[inline(always)]
+    pub const fn remaining_capacity(&self) -> usize {
+        MAX_SIZE - self.size
+    }
+
+    #[inline(always)]
+    pub const fn is_full(&self) -> bool {
+        self.size == MAX_SIZE
+    }
+
     pub fn push(&mut self, value: T) {
-        if self.size >= MAX_SIZE {
+        if self.try_push(value).is_err() {
             panic!("Capacity exceeded");
         }
+    }
+
+    /// Appends `value`, handing it back inside the error if the vector is full.
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.size >= MAX_SIZE {
+            return Err(CapacityError::new(value));
+        }
         unsafe {
             ptr::write(self.data[self.size].as_mut_ptr(), value);
         }
         self.size += 1;
+        Ok(())
+    }
+
+    /// Inserts `value` at `index`, shifting later elements to the right.
+    ///
+    /// Panics if `index > len`, like `Vec::insert`.
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), CapacityError<T>> {
+        assert!(index <= self.size, "Index out of bounds");
+        if self.size >= MAX_SIZE {
+            return Err(CapacityError::new(value));
+        }
+        unsafe {
+            // SAFETY: There is room for one more element and `index <= size`
+            let p = self.data.as_mut_ptr().add(index) as *mut T;
+            ptr::copy(p, p.add(1), self.size - index);
+            ptr::write(p, value);
+        }
+        self.size += 1;
+        Ok(())
+    }
+
+    /// Clones every element of `other` onto the end of the vector.
+    ///
+    /// Either all elements are appended or, if they do not fit, none are.
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
+    where
+        T: Clone,
+    {
+        if other.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(()));
+        }
+        for value in other {
+            // Length is bumped per element so a panicking `clone` cannot leak
+            unsafe {
+                ptr::write(self.data[self.size].as_mut_ptr(), value.clone());
+            }
+            self.size += 1;
+        }
+        Ok(())
     }
 
     pub fn pop(&mut self) -> Option<T> {
@@ -46,13 +181,263 @@ impl<T, const MAX_SIZE: usize> StackVector<T, MAX_SIZE> {
     }
 
     pub fn clear(&mut self) {
-        while let Some(_) = self.pop() {}
+        self.truncate(0);
+    }
+
+    pub const fn as_slice(&self) -> &[T] {
+        // SAFETY: Elements are initialized up to `self.size`
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const T, self.size) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: Elements are initialized up to `self.size`
+        unsafe { slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.size) }
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        if self.try_insert(index, value).is_err() {
+            panic!("Capacity exceeded");
+        }
+    }
+
+    /// Removes and returns the element at `index`, shifting later elements to the left.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.size, "Index out of bounds");
+        unsafe {
+            // SAFETY: `index` is in bounds, the hole is closed before `size` is updated
+            let p = self.data.as_mut_ptr().add(index) as *mut T;
+            let value = ptr::read(p);
+            ptr::copy(p.add(1), p, self.size - index - 1);
+            self.size -= 1;
+            value
+        }
+    }
+
+    /// Removes and returns the element at `index`, replacing it with the last element.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        assert!(index < self.size, "Index out of bounds");
+        let last = self.size - 1;
+        self.as_mut_slice().swap(index, last);
+        self.size = last;
+        unsafe { ptr::read(self.data[last].as_ptr()) }
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.size {
+            return;
+        }
+        let tail_len = self.size - len;
+        // Length is updated first so a panicking destructor cannot cause a double drop
+        self.size = len;
+        unsafe {
+            let tail = self.data.as_mut_ptr().add(len) as *mut T;
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(tail, tail_len));
+        }
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|value| f(value));
+    }
+
+    /// Keeps only the elements for which `f` returns `true`, preserving order.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        struct BackshiftOnDrop<'a, T, const MAX_SIZE: usize> {
+            vector: &'a mut StackVector<T, MAX_SIZE>,
+            processed: usize,
+            deleted: usize,
+            original_len: usize,
+        }
+
+        impl<T, const MAX_SIZE: usize> Drop for BackshiftOnDrop<'_, T, MAX_SIZE> {
+            fn drop(&mut self) {
+                if self.deleted > 0 {
+                    // Close the gap left by deleted elements, including any unprocessed tail
+                    unsafe {
+                        let base = self.vector.data.as_mut_ptr() as *mut T;
+                        ptr::copy(
+                            base.add(self.processed),
+                            base.add(self.processed - self.deleted),
+                            self.original_len - self.processed,
+                        );
+                    }
+                }
+                self.vector.size = self.original_len - self.deleted;
+            }
+        }
+
+        let original_len = self.size;
+        // Hide all elements until the guard restores a consistent length
+        self.size = 0;
+        let mut guard = BackshiftOnDrop {
+            vector: self,
+            processed: 0,
+            deleted: 0,
+            original_len,
+        };
+
+        while guard.processed < original_len {
+            unsafe {
+                let base = guard.vector.data.as_mut_ptr() as *mut T;
+                let current = base.add(guard.processed);
+                if !f(&mut *current) {
+                    guard.processed += 1;
+                    guard.deleted += 1;
+                    ptr::drop_in_place(current);
+                } else {
+                    if guard.deleted > 0 {
+                        ptr::copy_nonoverlapping(
+                            current,
+                            base.add(guard.processed - guard.deleted),
+                            1,
+                        );
+                    }
+                    guard.processed += 1;
+                }
+            }
+        }
+    }
+
+    /// Removes consecutive elements for which `same_bucket(current, previous)` returns `true`.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        struct FillGapOnDrop<'a, T, const MAX_SIZE: usize> {
+            vector: &'a mut StackVector<T, MAX_SIZE>,
+            read: usize,
+            write: usize,
+            original_len: usize,
+        }
+
+        impl<T, const MAX_SIZE: usize> Drop for FillGapOnDrop<'_, T, MAX_SIZE> {
+            fn drop(&mut self) {
+                let remaining = self.original_len - self.read;
+                unsafe {
+                    let base = self.vector.data.as_mut_ptr() as *mut T;
+                    ptr::copy(base.add(self.read), base.add(self.write), remaining);
+                }
+                self.vector.size = self.write + remaining;
+            }
+        }
+
+        let original_len = self.size;
+        if original_len <= 1 {
+            return;
+        }
+        self.size = 0;
+        let mut guard = FillGapOnDrop {
+            vector: self,
+            read: 1,
+            write: 1,
+            original_len,
+        };
+
+        while guard.read < original_len {
+            unsafe {
+                let base = guard.vector.data.as_mut_ptr() as *mut T;
+                let current = base.add(guard.read);
+                let previous = base.add(guard.write - 1);
+                if same_bucket(&mut *current, &mut *previous) {
+                    guard.read += 1;
+                    ptr::drop_in_place(current);
+                } else {
+                    ptr::copy(current, base.add(guard.write), 1);
+                    guard.write += 1;
+                    guard.read += 1;
+                }
+            }
+        }
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Moves the elements from `at` onwards into a new vector.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.size, "Index out of bounds");
+        let mut other = Self::new();
+        let other_len = self.size - at;
+        unsafe {
+            let src = self.data.as_ptr().add(at) as *const T;
+            ptr::copy_nonoverlapping(src, other.data.as_mut_ptr() as *mut T, other_len);
+        }
+        self.size = at;
+        other.size = other_len;
+        other
+    }
+
+    /// Resizes the vector to `new_len`, filling new slots with values returned by `f`.
+    ///
+    /// Panics if `new_len` exceeds `MAX_SIZE`.
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        assert!(new_len <= MAX_SIZE, "Capacity exceeded");
+        if new_len <= self.size {
+            self.truncate(new_len);
+        } else {
+            while self.size < new_len {
+                self.push(f());
+            }
+        }
+    }
+
+    /// Removes the elements in `range` and returns them as an iterator.
+    ///
+    /// Elements not consumed by the iterator are dropped when it goes out of scope.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, MAX_SIZE>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.size;
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(start <= end, "Drain start is greater than end");
+        assert!(end <= len, "Index out of bounds");
+
+        // Elements past `start` are owned by the drain until it is dropped
+        self.size = start;
+        Drain {
+            vector: self,
+            start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+        }
     }
 }
 
 impl<T, const MAX_SIZE: usize> Drop for StackVector<T, MAX_SIZE> {
     fn drop(&mut self) {
-        while let Some(_) = self.pop() {}
+        self.clear();
     }
 }
 
@@ -72,25 +457,142 @@ impl<T: fmt::Debug, const MAX_SIZE: usize> fmt::Debug for StackVector<T, MAX_SIZ
     }
 }
 
-impl<T, const MAX_SIZE: usize> Index<usize> for StackVector<T, MAX_SIZE> {
-    type Output = T;
+impl<T, I: SliceIndex<[T]>, const MAX_SIZE: usize> Index<I> for StackVector<T, MAX_SIZE> {
+    type Output = I::Output;
 
-    fn index(&self, index: usize) -> &Self::Output {
-        assert!(index < self.size, "Index out of bounds");
-        unsafe { self.data[index].assume_init_ref() }
+    fn index(&self, index: I) -> &Self::Output {
+        Index::index(self.as_slice(), index)
     }
 }
 
-impl<T, const MAX_SIZE: usize> IndexMut<usize> for StackVector<T, MAX_SIZE> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        assert!(index < self.size, "Index out of bounds");
-        unsafe { self.data[index].assume_init_mut() }
+impl<T, I: SliceIndex<[T]>, const MAX_SIZE: usize> IndexMut<I> for StackVector<T, MAX_SIZE> {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        IndexMut::index_mut(self.as_mut_slice(), index)
+    }
+}
+
+impl<T, const MAX_SIZE: usize> Deref for StackVector<T, MAX_SIZE> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const MAX_SIZE: usize> DerefMut for StackVector<T, MAX_SIZE> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const MAX_SIZE: usize> AsRef<[T]> for StackVector<T, MAX_SIZE> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const MAX_SIZE: usize> AsMut<[T]> for StackVector<T, MAX_SIZE> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const MAX_SIZE: usize> Borrow<[T]> for StackVector<T, MAX_SIZE> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const MAX_SIZE: usize> BorrowMut<[T]> for StackVector<T, MAX_SIZE> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+pub struct Drain<'a, T, const MAX_SIZE: usize> {
+    vector: &'a mut StackVector<T, MAX_SIZE>,
+    start: usize,
+    end: usize,
+    tail_start: usize,
+    tail_len: usize,
+}
+
+impl<T, const MAX_SIZE: usize> Drain<'_, T, MAX_SIZE> {
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: Elements in `[start, end)` have not been yielded yet
+        unsafe {
+            let base = self.vector.data.as_ptr() as *const T;
+            slice::from_raw_parts(base.add(self.start), self.end - self.start)
+        }
     }
 }
 
+impl<T, const MAX_SIZE: usize> Iterator for Drain<'_, T, MAX_SIZE> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            let value = unsafe { ptr::read(self.vector.data[self.start].as_ptr()) };
+            self.start += 1;
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, const MAX_SIZE: usize> DoubleEndedIterator for Drain<'_, T, MAX_SIZE> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            self.end -= 1;
+            Some(unsafe { ptr::read(self.vector.data[self.end].as_ptr()) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const MAX_SIZE: usize> ExactSizeIterator for Drain<'_, T, MAX_SIZE> {}
+
+impl<T, const MAX_SIZE: usize> Drop for Drain<'_, T, MAX_SIZE> {
+    fn drop(&mut self) {
+        // Moves the kept tail back into place even if an element destructor panics
+        struct MoveTail<'r, 'a, T, const MAX_SIZE: usize>(&'r mut Drain<'a, T, MAX_SIZE>);
+
+        impl<T, const MAX_SIZE: usize> Drop for MoveTail<'_, '_, T, MAX_SIZE> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let len = drain.vector.size;
+                unsafe {
+                    let base = drain.vector.data.as_mut_ptr() as *mut T;
+                    ptr::copy(base.add(drain.tail_start), base.add(len), drain.tail_len);
+                }
+                drain.vector.size = len + drain.tail_len;
+            }
+        }
+
+        let guard = MoveTail(self);
+        while guard.0.start < guard.0.end {
+            let index = guard.0.start;
+            guard.0.start += 1;
+            unsafe { ptr::drop_in_place(guard.0.vector.data[index].as_mut_ptr()) };
+        }
+    }
+}
+
+/// By-value iterator over a `StackVector`.
+///
+/// Owns the storage directly and tracks the live `[start, end)` range, so each
+/// element is either yielded exactly once or dropped with the iterator.
 pub struct StackVectorIntoIterator<T, const MAX_SIZE: usize> {
-    vector: StackVector<T, MAX_SIZE>,
-    index: usize,
+    data: [mem::MaybeUninit<T>; MAX_SIZE],
+    start: usize,
+    end: usize,
 }
 
 impl<T, const MAX_SIZE: usize> IntoIterator for StackVector<T, MAX_SIZE> {
@@ -98,9 +600,49 @@ impl<T, const MAX_SIZE: usize> IntoIterator for StackVector<T, MAX_SIZE> {
     type IntoIter = StackVectorIntoIterator<T, MAX_SIZE>;
 
     fn into_iter(self) -> Self::IntoIter {
+        // The vector must not run its own `Drop`, ownership of the elements moves to the iterator
+        let vector = mem::ManuallyDrop::new(self);
         StackVectorIntoIterator {
-            vector: self,
-            index: 0,
+            data: unsafe { ptr::read(&vector.data) },
+            start: 0,
+            end: vector.size,
+        }
+    }
+}
+
+impl<'a, T, const MAX_SIZE: usize> IntoIterator for &'a StackVector<T, MAX_SIZE> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a, T, const MAX_SIZE: usize> IntoIterator for &'a mut StackVector<T, MAX_SIZE> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl<T, const MAX_SIZE: usize> StackVectorIntoIterator<T, MAX_SIZE> {
+    /// Returns the elements that have not been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: Elements in `[start, end)` are initialized and not yet yielded
+        unsafe {
+            let base = self.data.as_ptr() as *const T;
+            slice::from_raw_parts(base.add(self.start), self.end - self.start)
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: Elements in `[start, end)` are initialized and not yet yielded
+        unsafe {
+            let base = self.data.as_mut_ptr() as *mut T;
+            slice::from_raw_parts_mut(base.add(self.start), self.end - self.start)
         }
     }
 }
@@ -109,31 +651,68 @@ impl<T, const MAX_SIZE: usize> Iterator for StackVectorIntoIterator<T, MAX_SIZE>
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.vector.size {
-            let value = unsafe { ptr::read(self.vector.data[self.index].as_ptr()) };
-            self.index += 1;
+        if self.start < self.end {
+            let value = unsafe { ptr::read(self.data[self.start].as_ptr()) };
+            self.start += 1;
             Some(value)
         } else {
             None
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, const MAX_SIZE: usize> DoubleEndedIterator for StackVectorIntoIterator<T, MAX_SIZE> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            self.end -= 1;
+            Some(unsafe { ptr::read(self.data[self.end].as_ptr()) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const MAX_SIZE: usize> ExactSizeIterator for StackVectorIntoIterator<T, MAX_SIZE> {}
+
+impl<T, const MAX_SIZE: usize> FusedIterator for StackVectorIntoIterator<T, MAX_SIZE> {}
+
+impl<T: Clone, const MAX_SIZE: usize> Clone for StackVectorIntoIterator<T, MAX_SIZE> {
+    fn clone(&self) -> Self {
+        let mut vector = StackVector::new();
+        for value in self.as_slice() {
+            vector.push(value.clone());
+        }
+        vector.into_iter()
+    }
+}
+
+impl<T: fmt::Debug, const MAX_SIZE: usize> fmt::Debug for StackVectorIntoIterator<T, MAX_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StackVectorIntoIterator")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+impl<T, const MAX_SIZE: usize> Drop for StackVectorIntoIterator<T, MAX_SIZE> {
+    fn drop(&mut self) {
+        // SAFETY: Only the elements that were never yielded are dropped
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
 }
 
 impl<T, const MAX_SIZE: usize> StackVector<T, MAX_SIZE> {
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
-        unsafe {
-            // SAFETY: Assuming elements are initialized up to `self.size`
-            let slice = slice::from_raw_parts(self.data.as_ptr() as *const T, self.size);
-            slice.iter()
-        }
+        self.as_slice().iter()
     }
 
     pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut T> {
-        unsafe {
-            // SAFETY: Assuming elements are initialized up to `self.size`
-            let slice = slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.size);
-            slice.iter_mut()
-        }
+        self.as_mut_slice().iter_mut()
     }
 }
 
@@ -160,3 +739,141 @@ impl<T, const MAX_SIZE: usize> Default for StackVector<T, MAX_SIZE> {
         Self::new()
     }
 }
+
+impl<T: PartialOrd, const MAX_SIZE: usize> PartialOrd for StackVector<T, MAX_SIZE> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, const MAX_SIZE: usize> Ord for StackVector<T, MAX_SIZE> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Hash, const MAX_SIZE: usize> Hash for StackVector<T, MAX_SIZE> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Must match the slice hash since the vector implements `Borrow<[T]>`
+        Hash::hash(self.as_slice(), state)
+    }
+}
+
+impl<T, const MAX_SIZE: usize> Extend<T> for StackVector<T, MAX_SIZE> {
+    /// Panics if the items do not fit, like `push`.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, const MAX_SIZE: usize> Extend<&'a T> for StackVector<T, MAX_SIZE> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const MAX_SIZE: usize> FromIterator<T> for StackVector<T, MAX_SIZE> {
+    /// Panics if the iterator yields more than `MAX_SIZE` items.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = Self::new();
+        vector.extend(iter);
+        vector
+    }
+}
+
+impl<T: Clone, const MAX_SIZE: usize> TryFrom<&[T]> for StackVector<T, MAX_SIZE> {
+    type Error = CapacityError;
+
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        let mut vector = Self::new();
+        vector.try_extend_from_slice(slice)?;
+        Ok(vector)
+    }
+}
+
+impl<T, const MAX_SIZE: usize> TryFrom<Vec<T>> for StackVector<T, MAX_SIZE> {
+    /// Hands the vector back untouched if it is too long.
+    type Error = CapacityError<Vec<T>>;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        if vec.len() > MAX_SIZE {
+            return Err(CapacityError::new(vec));
+        }
+        Ok(vec.into_iter().collect())
+    }
+}
+
+impl<T, const MAX_SIZE: usize> From<[T; MAX_SIZE]> for StackVector<T, MAX_SIZE> {
+    fn from(array: [T; MAX_SIZE]) -> Self {
+        Self::from_array(array)
+    }
+}
+
+impl<T, const MAX_SIZE: usize> From<StackVector<T, MAX_SIZE>> for Vec<T> {
+    fn from(vector: StackVector<T, MAX_SIZE>) -> Self {
+        let mut vec = Vec::with_capacity(vector.len());
+        vec.extend(vector);
+        vec
+    }
+}
+
+impl<const MAX_SIZE: usize> io::Write for StackVector<u8, MAX_SIZE> {
+    /// Writes as many bytes as fit, returning `Ok(0)` once the vector is full.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let count = buf.len().min(self.remaining_capacity());
+        // Cannot fail, `count` is bounded by the remaining capacity
+        let _ = self.try_extend_from_slice(&buf[..count]);
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use super::StackVector;
+    use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    impl<T: Serialize, const MAX_SIZE: usize> Serialize for StackVector<T, MAX_SIZE> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>, const MAX_SIZE: usize> Deserialize<'de>
+        for StackVector<T, MAX_SIZE>
+    {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct StackVectorVisitor<T, const MAX_SIZE: usize>(PhantomData<T>);
+
+            impl<'de, T: Deserialize<'de>, const MAX_SIZE: usize> Visitor<'de>
+                for StackVectorVisitor<T, MAX_SIZE>
+            {
+                type Value = StackVector<T, MAX_SIZE>;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a sequence of at most {} elements", MAX_SIZE)
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut vector = StackVector::new();
+                    while let Some(value) = seq.next_element()? {
+                        if vector.try_push(value).is_err() {
+                            return Err(A::Error::invalid_length(MAX_SIZE + 1, &self));
+                        }
+                    }
+                    Ok(vector)
+                }
+            }
+
+            deserializer.deserialize_seq(StackVectorVisitor::<T, MAX_SIZE>(PhantomData))
+        }
+    }
+}
@@ -1,4 +1,5 @@
 fn main() {
+    #[allow(dead_code)]
     enum Direction {
         Up,
         Down,
@@ -0,0 +1,251 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Index, LitStr, Path, Result,
+};
+
+/// Derives `challenges::serialization::Serializable`.
+///
+/// Structs encode their fields in declaration order. Enums encode the index of
+/// the variant in declaration order as a `u32` tag, followed by its fields.
+/// Tags are declaration indices, so reordering, inserting or removing variants
+/// other than at the end changes the wire format.
+///
+/// Decoding counts as one level of `Deserializer::nested`, so recursive types
+/// stop at `Deserializer::max_depth`.
+///
+/// Type attribute:
+///
+/// - `#[serializable(crate = "path")]`: where to find the `challenges` crate,
+///   `::challenges` by default, for dependents that rename it
+///
+/// Field attributes:
+///
+/// - `#[serializable(skip)]`: never written, decoded as `Default::default()`
+/// - `#[serializable(default)]`: decoded as `Default::default()` if the input ends
+///   right before the field. Only allowed on trailing fields, followed by nothing
+///   but other `default` or `skip` fields. This lets fields be added to a type
+///   that is the last value in its input, such as the outermost value passed to
+///   `from_bytes`. Data holding the type inside a `Vec`, a map, another struct or
+///   followed by more values in the same stream cannot be read this way, since
+///   the new field would take the bytes of whatever comes next.
+#[proc_macro_derive(Serializable, attributes(serializable))]
+pub fn derive_serializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Encode,
+    Skip,
+    Default,
+}
+
+/// Path of the `serialization` module, from the type's `#[serializable(crate = "...")]`.
+fn serialization_path(input: &DeriveInput) -> Result<TokenStream2> {
+    let mut krate: Path = parse_quote!(::challenges);
+    for attr in &input.attrs {
+        if !attr.path().is_ident("serializable") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("crate") {
+                return Err(meta.error("expected `crate`"));
+            }
+            krate = meta.value()?.parse::<LitStr>()?.parse()?;
+            Ok(())
+        })?;
+    }
+    Ok(quote! { #krate::serialization })
+}
+
+fn field_modes(fields: &Fields) -> Result<Vec<Mode>> {
+    let modes = fields
+        .iter()
+        .map(|field| {
+            let mut mode = Mode::Encode;
+            for attr in &field.attrs {
+                if !attr.path().is_ident("serializable") {
+                    continue;
+                }
+                attr.parse_nested_meta(|meta| {
+                    let next = if meta.path.is_ident("skip") {
+                        Mode::Skip
+                    } else if meta.path.is_ident("default") {
+                        Mode::Default
+                    } else {
+                        return Err(meta.error("expected `skip` or `default`"));
+                    };
+                    if mode != Mode::Encode {
+                        return Err(meta.error("only one of `skip` or `default` is allowed"));
+                    }
+                    mode = next;
+                    Ok(())
+                })?;
+            }
+            Ok(mode)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // `deserialize_or_default` only sees the end of the input after the last field
+    if let Some(first) = modes.iter().position(|mode| *mode == Mode::Default) {
+        if let Some(index) = modes[first..].iter().position(|mode| *mode == Mode::Encode) {
+            let field = fields.iter().nth(first + index).unwrap();
+            return Err(Error::new_spanned(
+                field,
+                "fields after a `#[serializable(default)]` field must also be `default` or `skip`",
+            ));
+        }
+    }
+    Ok(modes)
+}
+
+/// Statements writing every encoded field, `values` are references to the fields.
+fn serialize_fields(modes: &[Mode], values: &[TokenStream2]) -> TokenStream2 {
+    let values = modes
+        .iter()
+        .zip(values)
+        .filter(|(mode, _)| **mode != Mode::Skip)
+        .map(|(_, value)| value);
+    quote! { #(__serializer.serialize(#values)?;)* }
+}
+
+/// Expression building `path` from the stream.
+fn deserialize_fields(path: TokenStream2, fields: &Fields, modes: &[Mode]) -> TokenStream2 {
+    let values = modes.iter().map(|mode| match mode {
+        Mode::Encode => quote! { __deserializer.deserialize()? },
+        Mode::Skip => quote! { ::core::default::Default::default() },
+        Mode::Default => quote! { __deserializer.deserialize_or_default()? },
+    });
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote! { #path { #(#names: #values),* } }
+        }
+        Fields::Unnamed(_) => quote! { #path ( #(#values),* ) },
+        Fields::Unit => path,
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let serialization = serialization_path(&input)?;
+    let (serialize, deserialize) = match &input.data {
+        Data::Struct(data) => {
+            let modes = field_modes(&data.fields)?;
+            let values: Vec<_> = data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| match &field.ident {
+                    Some(name) => quote! { &self.#name },
+                    None => {
+                        let index = Index::from(index);
+                        quote! { &self.#index }
+                    }
+                })
+                .collect();
+            let serialize = serialize_fields(&modes, &values);
+            let construct = deserialize_fields(quote! { Self }, &data.fields, &modes);
+            (
+                quote! { #serialize ::std::result::Result::Ok(()) },
+                quote! { ::std::result::Result::Ok(#construct) },
+            )
+        }
+        Data::Enum(data) => {
+            let mut serialize_arms = Vec::new();
+            let mut deserialize_arms = Vec::new();
+            for (tag, variant) in data.variants.iter().enumerate() {
+                let tag = u32::try_from(tag)
+                    .map_err(|_| Error::new_spanned(variant, "too many variants for a u32 tag"))?;
+                let name = &variant.ident;
+                let modes = field_modes(&variant.fields)?;
+                let bindings: Vec<_> = (0..modes.len())
+                    .map(|index| format_ident!("__field{}", index))
+                    .collect();
+                let patterns = modes.iter().zip(&bindings).map(|(mode, binding)| {
+                    if *mode == Mode::Skip {
+                        quote! { _ }
+                    } else {
+                        quote! { #binding }
+                    }
+                });
+                let pattern = match &variant.fields {
+                    Fields::Named(named) => {
+                        let names = named.named.iter().map(|field| &field.ident);
+                        quote! { Self::#name { #(#names: #patterns),* } }
+                    }
+                    Fields::Unnamed(_) => quote! { Self::#name ( #(#patterns),* ) },
+                    Fields::Unit => quote! { Self::#name },
+                };
+                let values: Vec<_> = bindings.iter().map(|binding| quote! { #binding }).collect();
+                let fields = serialize_fields(&modes, &values);
+                serialize_arms.push(quote! {
+                    #pattern => {
+                        __serializer.serialize(&#tag)?;
+                        #fields
+                    }
+                });
+                let construct = deserialize_fields(quote! { Self::#name }, &variant.fields, &modes);
+                deserialize_arms.push(quote! { #tag => ::std::result::Result::Ok(#construct), });
+            }
+            let serialize = if serialize_arms.is_empty() {
+                quote! { match *self {} }
+            } else {
+                quote! {
+                    match self { #(#serialize_arms)* }
+                    ::std::result::Result::Ok(())
+                }
+            };
+            let deserialize = quote! {
+                let __offset = __deserializer.position();
+                match __deserializer.deserialize::<u32>()? {
+                    #(#deserialize_arms)*
+                    __tag => ::std::result::Result::Err(
+                        #serialization::DecodeError::UnknownVariant {
+                            offset: __offset,
+                            tag: __tag,
+                        },
+                    ),
+                }
+            };
+            (serialize, deserialize)
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "Serializable cannot be derived for unions",
+            ))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param
+            .bounds
+            .push(parse_quote!(#serialization::Serializable));
+    }
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics #serialization::Serializable
+            for #name #type_generics #where_clause
+        {
+            fn custom_serialize<__W: ::std::io::Write>(
+                &self,
+                __serializer: &mut #serialization::Serializer<__W>,
+            ) -> ::std::io::Result<()> {
+                #serialize
+            }
+
+            fn custom_deserialize<__R: ::std::io::Read>(
+                __deserializer: &mut #serialization::Deserializer<__R>,
+            ) -> ::std::result::Result<Self, #serialization::DecodeError> {
+                __deserializer.nested(|__deserializer| { #deserialize })
+            }
+        }
+    })
+}
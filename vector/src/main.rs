@@ -1,4 +1,3 @@
-use rand;
 use std::iter::repeat_with;
 
 fn main() {
@@ -44,6 +43,6 @@ fn main() {
     println!("Vector of zeroes: {:?}", zeroes);
 
     // Create a vector of given length with a closure generated value
-    let random_numbers: Vec<i32> = repeat_with(|| rand::random::<i32>()).take(5).collect();
+    let random_numbers: Vec<i32> = repeat_with(rand::random::<i32>).take(5).collect();
     println!("Vector of random numbers: {:?}", random_numbers);
 }